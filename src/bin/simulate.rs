@@ -0,0 +1,184 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use werewolf_rust::game::simulation::{
+    composition_from_map, run_simulations, BotStrategy, SimulationReport,
+};
+use werewolf_rust::utils::role::{generate_role_composition, validate_banned_roles, RoleId};
+use werewolf_rust::utils::role_parser::{get_role_id_by_name, parse_roles_from_json_string};
+
+const USAGE: &str = "Cách dùng: simulate [--games N] [--seed S] [--strategy random|heuristic] \
+[--players N | --players MIN-MAX] [--ban role1,role2] [--roles '<json>' | --roles-file <path>]";
+
+struct Options {
+    games: u32,
+    seed: u64,
+    strategy: BotStrategy,
+    players: (usize, usize),
+    banned: Vec<RoleId>,
+    custom_roles: Option<String>,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        games: 1000,
+        seed: rand::random(),
+        strategy: BotStrategy::Heuristic,
+        players: (4, 18),
+        banned: Vec::new(),
+        custom_roles: None,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Thiếu giá trị cho {}", arg))
+        };
+
+        match arg.as_str() {
+            "--games" => {
+                options.games = value()?
+                    .parse()
+                    .map_err(|_| "--games phải là số dương".to_string())?;
+            }
+            "--seed" => {
+                options.seed = value()?
+                    .parse()
+                    .map_err(|_| "--seed phải là số".to_string())?;
+            }
+            "--strategy" => {
+                options.strategy = match value()?.as_str() {
+                    "random" => BotStrategy::Random,
+                    "heuristic" => BotStrategy::Heuristic,
+                    other => return Err(format!("Chiến thuật không hợp lệ: {}", other)),
+                };
+            }
+            "--players" => {
+                let raw = value()?;
+                let parse = |s: &str| {
+                    s.trim()
+                        .parse::<usize>()
+                        .map_err(|_| format!("Số người chơi không hợp lệ: {}", raw))
+                };
+                options.players = match raw.split_once('-') {
+                    Some((min, max)) => (parse(min)?, parse(max)?),
+                    None => {
+                        let n = parse(&raw)?;
+                        (n, n)
+                    }
+                };
+            }
+            "--ban" => {
+                for name in value()?.split(',') {
+                    let role_id = get_role_id_by_name(name)
+                        .and_then(RoleId::from_u8)
+                        .ok_or_else(|| format!("Không tìm thấy vai trò: {}", name))?;
+                    options.banned.push(role_id);
+                }
+                validate_banned_roles(&options.banned)?;
+            }
+            "--roles" => options.custom_roles = Some(value()?),
+            "--roles-file" => {
+                let path = value()?;
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| format!("Không đọc được {}: {}", path, e))?;
+                options.custom_roles = Some(content);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            other => return Err(format!("Tham số không hợp lệ: {}", other)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn print_report(title: &str, composition: &[RoleId], report: &SimulationReport) {
+    let roles = composition
+        .iter()
+        .map(|r| r.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    println!("=== {} ===", title);
+    println!("Đội hình: {}", roles);
+    println!(
+        "Số ván: {} | Dân Làng: {:.1}% | Ma Sói: {:.1}% | Solo: {:.1}% | Hoà: {:.1}% | Trung bình {:.1} vòng",
+        report.games,
+        report.rate(report.village_wins),
+        report.rate(report.werewolf_wins),
+        report.rate(report.solo_wins),
+        report.rate(report.draws),
+        report.average_rounds()
+    );
+
+    println!(
+        "{:<20} {:>10} {:>10}",
+        "Vai trò", "Lượt chơi", "Tỉ lệ thắng"
+    );
+    for (role_id, stats) in report.roles.iter() {
+        let name = RoleId::from_u8(*role_id).map(|r| r.name()).unwrap_or("?");
+        let win_rate = if stats.appearances == 0 {
+            0.0
+        } else {
+            stats.wins as f64 * 100.0 / stats.appearances as f64
+        };
+        println!("{:<20} {:>10} {:>9.1}%", name, stats.appearances, win_rate);
+    }
+    println!();
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("❌ {}\n{}", e, USAGE);
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "🎲 Seed: {} | Chiến thuật: {:?} | {} ván mỗi đội hình\n",
+        options.seed, options.strategy, options.games
+    );
+
+    if let Some(raw) = options.custom_roles.as_deref() {
+        let composition = match parse_roles_from_json_string(raw)
+            .and_then(|roles_map| composition_from_map(&roles_map))
+        {
+            Ok(composition) => composition,
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
+        };
+
+        let report = run_simulations(&composition, options.games, options.strategy, options.seed);
+        let title = format!("Đội hình tuỳ chỉnh ({} người)", composition.len());
+        print_report(&title, &composition, &report);
+        return;
+    }
+
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let (min, max) = options.players;
+    for players in min..=max {
+        let composition = match generate_role_composition(players, &options.banned, &mut rng)
+            .and_then(|roles_map| composition_from_map(&roles_map))
+        {
+            Ok(composition) => composition,
+            Err(e) => {
+                eprintln!("⚠️ {}", e);
+                continue;
+            }
+        };
+
+        let report = run_simulations(&composition, options.games, options.strategy, options.seed);
+        print_report(&format!("{} người chơi", players), &composition, &report);
+    }
+}
@@ -1,14 +1,15 @@
 use chrono::Utc;
+use mongodb::bson::oid::ObjectId;
 use serenity::all::{
-    ChannelId, ComponentInteraction, CreateSelectMenu, CreateSelectMenuKind,
+    ChannelId, Command, ComponentInteraction, CreateSelectMenu, CreateSelectMenuKind,
     CreateSelectMenuOption, MessageId, UserId,
 };
 use serenity::builder::EditInteractionResponse;
 use serenity::{
     all::{
-        ActionRowComponent, ComponentInteractionDataKind, CreateActionRow, CreateInputText,
-        CreateInteractionResponse, CreateInteractionResponseMessage, CreateModal, GuildId,
-        InputTextStyle, Interaction, Message, Ready,
+        ActionRow, ActionRowComponent, ButtonStyle, ComponentInteractionDataKind, CreateActionRow,
+        CreateButton, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
+        CreateModal, GuildId, InputTextStyle, Interaction, Message, Ready,
     },
     async_trait,
     prelude::*,
@@ -16,19 +17,30 @@ use serenity::{
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::oneshot;
 
-use crate::constants::MAX_FILE_SIZE;
+use crate::constants::{MAX_AFK_THRESHOLD, MAX_FILE_SIZE};
 use crate::game::room::RoomHandle;
 use crate::game::{ChatFile, MessageTypeStore};
 use crate::types::Player;
 use crate::{
     bot::BotData,
-    commands::{all_commands, role::get_role_menu_row},
-    game::{RoomEvent, RoomSnapshot, StartGameResult},
+    commands::{
+        all_commands,
+        history::record_attachments,
+        join::join_room,
+        leave::leave_room,
+        role::role_info_message,
+        settings::{
+            apply_settings_modal, is_admin, settings_components, settings_embed, settings_modal,
+        },
+        start::start_options_row,
+    },
+    db::ServerSettings,
+    game::{HostActionResult, ReadyResult, RoomEvent, RoomSnapshot, StartGameResult},
     types::types::InteractionWrapper,
     utils::{
-        embed::create_werewolf_embed,
-        role::convert_faction_role,
+        role::{validate_banned_roles, RoleId},
         role_parser::{parse_roles_from_json_string, parse_roles_from_string},
+        role_validator::validate_composition,
     },
 };
 use crate::{client::handler::command_handler, commands::guide::get_guide_content};
@@ -159,13 +171,178 @@ impl Handler {
         }
     }
 
+    async fn handle_hang_vote_menu(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+    ) -> Option<(ChannelId, MessageId)> {
+        let custom_id = &component.data.custom_id;
+
+        let owner_id_str = custom_id.strip_prefix("vote_execution_req_").unwrap_or("");
+        if component.user.id.to_string() != owner_id_str {
+            self.reply_error(ctx, component, "❌ Nút này không phải của bạn!")
+                .await;
+            return None;
+        }
+
+        let room_handle = match self.get_room_handle_by_user(component.user.id).await {
+            Some(h) => h,
+            None => {
+                self.reply_error(
+                    ctx,
+                    component,
+                    "❌ Bạn không trong ván game nào/Phòng đã xóa.",
+                )
+                .await;
+                return None;
+            }
+        };
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        if room_handle
+            .sender
+            .send(RoomEvent::GetAllPlayers { reply: tx })
+            .is_err()
+        {
+            return None;
+        }
+        let all_players = rx.await.ok()?;
+
+        let mut options = vec![CreateSelectMenuOption::new("Bỏ qua", "skip").emoji('⏭')];
+        for (index, player) in all_players
+            .iter()
+            .filter(|p| p.alive && p.user_id != component.user.id)
+            .enumerate()
+        {
+            let label = format!("{}. {}", index + 1, player.name);
+            options
+                .push(CreateSelectMenuOption::new(label, player.user_id.to_string()).emoji('👤'));
+        }
+        options.truncate(25);
+
+        let select_menu = CreateSelectMenu::new(
+            "vote_hanged_submit",
+            CreateSelectMenuKind::String { options },
+        )
+        .placeholder("⚖️ Chọn người cần treo cổ...")
+        .min_values(1)
+        .max_values(1);
+
+        if let Err(e) = component
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content("Hãy chọn người bạn muốn treo cổ (hoặc bỏ qua):")
+                        .select_menu(select_menu),
+                ),
+            )
+            .await
+        {
+            tracing::error!("Lỗi gửi menu: {:?}", e);
+            return None;
+        }
+
+        match component.get_response(&ctx.http).await {
+            Ok(msg) => Some((msg.channel_id, msg.id)),
+            Err(e) => {
+                tracing::error!("Không lấy được response message: {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Xử lý menu chọn 1 mục tiêu: đọc lựa chọn, gửi event vào phòng và cập nhật tin nhắn.
+    async fn handle_single_target_submit<F>(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+        cancel_text: &str,
+        success_text: &str,
+        make_event: F,
+    ) where
+        F: FnOnce(UserId) -> RoomEvent,
+    {
+        let values = match &component.data.kind {
+            ComponentInteractionDataKind::StringSelect { values } => values,
+            _ => return,
+        };
+
+        let first_value = match values.first() {
+            Some(v) => v,
+            None => return,
+        };
+
+        if first_value == "cancel_action" {
+            let _ = component
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .content(cancel_text)
+                            .components(vec![]),
+                    ),
+                )
+                .await;
+            return;
+        }
+
+        let target_id: UserId = match first_value.parse::<u64>() {
+            Ok(id) => UserId::new(id),
+            Err(_) => return,
+        };
+
+        let room_handle = match self.get_room_handle_by_user(component.user.id).await {
+            Some(h) => h,
+            None => {
+                self.reply_error(ctx, component, "❌ Lỗi: Không tìm thấy phòng.")
+                    .await;
+                return;
+            }
+        };
+
+        if room_handle.sender.send(make_event(target_id)).is_err() {
+            self.reply_error(ctx, component, "❌ Lỗi: Game đã kết thúc.")
+                .await;
+            return;
+        }
+
+        let _ = component
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .content(success_text.replace("{target}", &format!("<@{}>", target_id)))
+                        .components(vec![]),
+                ),
+            )
+            .await;
+    }
+
+    async fn register_night_message(
+        &self,
+        user_id: UserId,
+        msg_info: Option<(ChannelId, MessageId)>,
+    ) {
+        if let Some((channel_id, message_id)) = msg_info {
+            if let Some(room_handle) = self.get_room_handle_by_user(user_id).await {
+                let _ = room_handle.sender.send(RoomEvent::RegisterInteraction {
+                    user_id,
+                    channel_id,
+                    message_id,
+                    message_type_store: MessageTypeStore::NightMessage,
+                });
+            }
+        }
+    }
+
     async fn get_room_handle_by_user(&self, user_id: UserId) -> Option<RoomHandle> {
         let p_reg = self.data.player_registry.read().await;
-        let guild_id = *p_reg.get(&user_id)?;
+        let channel_id = *p_reg.get(&user_id)?;
         drop(p_reg);
 
         let r_reg = self.data.room_registry.read().await;
-        r_reg.get(&guild_id).cloned()
+        r_reg.get(&channel_id).cloned()
     }
 
     async fn reply_error(&self, ctx: &Context, component: &ComponentInteraction, msg: &str) {
@@ -664,6 +841,311 @@ impl EventHandler for Handler {
                     }
                 }
 
+                if custom_id.starts_with("accuse_req_") {
+                    let msg_info = self
+                        .handle_target_selection_menu(
+                            &ctx,
+                            &component,
+                            "accuse_req_",
+                            "accuse_submit",
+                            "☝️ Chọn người cần tố cáo...",
+                            1,
+                            1,
+                            |p| p.alive && p.user_id != component.user.id,
+                        )
+                        .await;
+
+                    if let Some((channel_id, message_id)) = msg_info {
+                        if let Some(room_handle) =
+                            self.get_room_handle_by_user(component.user.id).await
+                        {
+                            let _ = room_handle.sender.send(RoomEvent::RegisterInteraction {
+                                user_id: component.user.id,
+                                channel_id,
+                                message_id,
+                                message_type_store: MessageTypeStore::DayMessage,
+                            });
+                        }
+                    }
+                    return;
+                }
+
+                if custom_id == "accuse_submit" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ lời tố cáo.",
+                        "✅ ☝️ Bạn đã tố cáo {target}.",
+                        |target| RoomEvent::Accuse { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
+                if custom_id == "judgment_guilty" || custom_id == "judgment_innocent" {
+                    let guilty = custom_id == "judgment_guilty";
+
+                    let room_handle = match self.get_room_handle_by_user(component.user.id).await {
+                        Some(h) => h,
+                        None => {
+                            self.reply_error(&ctx, &component, "❌ Lỗi: Không tìm thấy phòng.")
+                                .await;
+                            return;
+                        }
+                    };
+
+                    let event = RoomEvent::JudgmentVote {
+                        user_id: component.user.id,
+                        guilty,
+                    };
+                    if room_handle.sender.send(event).is_err() {
+                        self.reply_error(&ctx, &component, "❌ Lỗi: Game đã kết thúc.")
+                            .await;
+                        return;
+                    }
+
+                    let content = if guilty {
+                        "👍 Bạn đã chọn **treo cổ**."
+                    } else {
+                        "👎 Bạn đã chọn **tha**."
+                    };
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new()
+                                    .content(content)
+                                    .components(vec![]),
+                            ),
+                        )
+                        .await;
+                    return;
+                }
+
+                if custom_id.starts_with("vote_execution_req_") {
+                    let msg_info = self.handle_hang_vote_menu(&ctx, &component).await;
+
+                    if let Some((channel_id, message_id)) = msg_info {
+                        if let Some(room_handle) =
+                            self.get_room_handle_by_user(component.user.id).await
+                        {
+                            let _ = room_handle.sender.send(RoomEvent::RegisterInteraction {
+                                user_id: component.user.id,
+                                channel_id,
+                                message_id,
+                                message_type_store: MessageTypeStore::VoteMessage,
+                            });
+                        }
+                    }
+                    return;
+                }
+
+                if custom_id == "vote_hanged_submit" {
+                    let values = match &component.data.kind {
+                        ComponentInteractionDataKind::StringSelect { values } => values,
+                        _ => return,
+                    };
+
+                    let target = match values.first() {
+                        Some(v) => v.clone(),
+                        None => return,
+                    };
+
+                    let room_handle = match self.get_room_handle_by_user(component.user.id).await {
+                        Some(h) => h,
+                        None => {
+                            self.reply_error(&ctx, &component, "❌ Lỗi: Không tìm thấy phòng.")
+                                .await;
+                            return;
+                        }
+                    };
+
+                    let content = if target == "skip" {
+                        "⏭️ Bạn đã bỏ qua lượt bỏ phiếu.".to_string()
+                    } else {
+                        format!("🗳️ Bạn đã bỏ phiếu treo cổ <@{}>.", target)
+                    };
+
+                    let event = RoomEvent::HangVote {
+                        user_id: component.user.id,
+                        target,
+                    };
+
+                    if room_handle.sender.send(event).is_err() {
+                        self.reply_error(&ctx, &component, "❌ Lỗi: Game đã kết thúc.")
+                            .await;
+                        return;
+                    }
+
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new()
+                                    .content(content)
+                                    .components(vec![]),
+                            ),
+                        )
+                        .await;
+
+                    return;
+                }
+
+                if custom_id.starts_with("poison_target_witch_") {
+                    let msg_info = self
+                        .handle_target_selection_menu(
+                            &ctx,
+                            &component,
+                            "poison_target_witch_",
+                            "witch_submit_poison",
+                            "💊 Chọn người cần đầu độc...",
+                            1,
+                            1,
+                            |p| p.alive && p.user_id != component.user.id,
+                        )
+                        .await;
+
+                    self.register_night_message(component.user.id, msg_info)
+                        .await;
+                    return;
+                }
+
+                if custom_id == "witch_submit_poison" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ hành động đầu độc.",
+                        "✅ 💊 Bạn đã chọn đầu độc {target}.",
+                        |target| RoomEvent::WitchPoison { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
+                if custom_id.starts_with("heal_target_witch_") {
+                    let msg_info = self
+                        .handle_target_selection_menu(
+                            &ctx,
+                            &component,
+                            "heal_target_witch_",
+                            "witch_submit_heal",
+                            "💫 Chọn người cần cứu...",
+                            1,
+                            1,
+                            |p| p.alive,
+                        )
+                        .await;
+
+                    self.register_night_message(component.user.id, msg_info)
+                        .await;
+                    return;
+                }
+
+                if custom_id == "witch_submit_heal" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ hành động cứu người.",
+                        "✅ 💫 Bạn đã chọn cứu {target}.",
+                        |target| RoomEvent::WitchHeal { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
+                if custom_id.starts_with("puppet_target_puppeteer_") {
+                    let msg_info = self
+                        .handle_target_selection_menu(
+                            &ctx,
+                            &component,
+                            "puppet_target_puppeteer_",
+                            "puppeteer_submit_target",
+                            "🎭 Chọn người sói phải ăn thịt...",
+                            1,
+                            1,
+                            |p| p.alive && p.user_id != component.user.id,
+                        )
+                        .await;
+
+                    self.register_night_message(component.user.id, msg_info)
+                        .await;
+                    return;
+                }
+
+                if custom_id == "puppeteer_submit_target" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ hành động chỉ định.",
+                        "✅ 🎭 Bạn đã chỉ định sói ăn thịt {target}.",
+                        |target| RoomEvent::PuppeteerTarget { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
+                if custom_id.starts_with("kill_target_stalker_") {
+                    let msg_info = self
+                        .handle_target_selection_menu(
+                            &ctx,
+                            &component,
+                            "kill_target_stalker_",
+                            "stalker_submit_kill",
+                            "🔪 Chọn người cần ám sát...",
+                            1,
+                            1,
+                            |p| p.alive && p.user_id != component.user.id,
+                        )
+                        .await;
+
+                    self.register_night_message(component.user.id, msg_info)
+                        .await;
+                    return;
+                }
+
+                if custom_id == "stalker_submit_kill" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ hành động ám sát.",
+                        "✅ 🔪 Bạn đã chọn ám sát {target}.",
+                        |target| RoomEvent::StalkerKill { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
+                if custom_id == "mayor_vote" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ phiếu bầu.",
+                        "✅ 🎖️ Bạn đã bầu {target} làm Thị Trưởng.",
+                        |target| RoomEvent::MayorVote { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
+                if custom_id == "mayor_successor" {
+                    let user_id = component.user.id;
+                    self.handle_single_target_submit(
+                        &ctx,
+                        &component,
+                        "❌ Đã hủy bỏ việc trao danh hiệu.",
+                        "✅ 🎖️ Bạn đã trao danh hiệu Thị Trưởng cho {target}.",
+                        |target| RoomEvent::MayorSuccessor { user_id, target },
+                    )
+                    .await;
+                    return;
+                }
+
                 if custom_id.starts_with("guide_select:") {
                     let owner_id = custom_id.split(":").last().unwrap_or("");
                     if component.user.id.to_string() != owner_id {
@@ -727,32 +1209,13 @@ impl EventHandler for Handler {
                     {
                         let selected_id = &values[0];
 
-                        if let Some(role) = self.data.roles_json.get(selected_id) {
-                            let file_name =
-                                format!("{}.png", role.e_name.to_lowercase().replace(" ", "_"));
-                            let faction_name = convert_faction_role(role.faction);
-
-                            let description =
-                                format!("{}\n\n**Phe:** {}", role.description, faction_name);
-                            let data_embed = create_werewolf_embed(
-                                &file_name,
-                                &format!("{} ({})", role.title, role.e_name),
-                                &description,
-                            )
-                            .await
-                            .unwrap();
-
-                            let row = get_role_menu_row(owner_id, &self.data.roles_json);
-
+                        if let Some(message) =
+                            role_info_message(selected_id, owner_id, &self.data.roles_json).await
+                        {
                             let _ = component
                                 .create_response(
                                     &ctx.http,
-                                    CreateInteractionResponse::UpdateMessage(
-                                        CreateInteractionResponseMessage::new()
-                                            .add_embed(data_embed.embed)
-                                            .add_file(data_embed.attachment)
-                                            .components(vec![row]),
-                                    ),
+                                    CreateInteractionResponse::UpdateMessage(message),
                                 )
                                 .await;
                         }
@@ -764,6 +1227,326 @@ impl EventHandler for Handler {
                     None => return,
                 };
 
+                if custom_id == "lobby_join" || custom_id == "lobby_leave" {
+                    // Embed phòng chờ đã hiển thị danh sách mới nên chỉ cần phản hồi riêng.
+                    let (content, _) = if custom_id == "lobby_join" {
+                        join_room(&self.data, guild_id, component.channel_id, &component.user).await
+                    } else {
+                        leave_room(
+                            &ctx.http,
+                            &self.data,
+                            component.channel_id,
+                            component.user.id,
+                        )
+                        .await
+                    };
+
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::Message(
+                                CreateInteractionResponseMessage::new()
+                                    .content(content)
+                                    .ephemeral(true),
+                            ),
+                        )
+                        .await;
+                    return;
+                }
+
+                if let Some(claim) = custom_id.strip_prefix("seat_claim:") {
+                    let Some((Ok(room_channel), Ok(seat))) =
+                        claim.split_once(':').map(|(channel, seat)| {
+                            (
+                                channel.parse::<u64>().map(ChannelId::new),
+                                seat.parse::<u64>().map(UserId::new),
+                            )
+                        })
+                    else {
+                        return;
+                    };
+
+                    let playing = self
+                        .data
+                        .player_registry
+                        .read()
+                        .await
+                        .get(&component.user.id)
+                        .copied();
+                    let handle = self
+                        .data
+                        .room_registry
+                        .read()
+                        .await
+                        .get(&room_channel)
+                        .cloned();
+
+                    let result = match (playing, handle) {
+                        (Some(channel_id), _) => Err(format!(
+                            "❌ Bạn đang tham gia một ván game ở kênh <#{}> rồi!",
+                            channel_id
+                        )),
+                        (None, None) => Err("❌ Phòng chơi đã đóng.".to_string()),
+                        (None, Some(handle)) => {
+                            let (tx, rx) = oneshot::channel();
+                            let _ = handle.sender.send(RoomEvent::ClaimSeat {
+                                seat,
+                                user_id: component.user.id,
+                                name: component.user.name.clone(),
+                                avatar_url: component.user.face(),
+                                reply: tx,
+                            });
+                            match rx.await {
+                                Ok(HostActionResult::Done(message)) => Ok(message),
+                                Ok(HostActionResult::Rejected(reason)) => {
+                                    Err(format!("❌ {}", reason))
+                                }
+                                Err(_) => Err("❌ Lỗi kết nối tới phòng chơi.".to_string()),
+                            }
+                        }
+                    };
+
+                    let response = match result {
+                        Ok(message) => CreateInteractionResponse::UpdateMessage(
+                            CreateInteractionResponseMessage::new()
+                                .content(message)
+                                .components(vec![]),
+                        ),
+                        Err(reason) => CreateInteractionResponse::Message(
+                            CreateInteractionResponseMessage::new()
+                                .content(reason)
+                                .ephemeral(true),
+                        ),
+                    };
+                    let _ = component.create_response(&ctx.http, response).await;
+                    return;
+                }
+
+                if custom_id == "lobby_ready" {
+                    let handle = self
+                        .data
+                        .room_registry
+                        .read()
+                        .await
+                        .get(&component.channel_id)
+                        .cloned();
+
+                    let result = match handle {
+                        Some(handle) => {
+                            let (tx, rx) = oneshot::channel();
+                            let _ = handle.sender.send(RoomEvent::ToggleReady {
+                                user_id: component.user.id,
+                                reply: tx,
+                            });
+                            rx.await.ok()
+                        }
+                        None => None,
+                    };
+
+                    let content = match result {
+                        Some(ReadyResult::Ready) => "✋ Bạn đã sẵn sàng!",
+                        Some(ReadyResult::Unready) => "↩️ Bạn đã huỷ trạng thái sẵn sàng.",
+                        Some(ReadyResult::NotJoined) => "⚠️ Bạn chưa tham gia phòng chơi này.",
+                        Some(ReadyResult::Unavailable) => {
+                            "⚠️ Phòng không bật kiểm tra sẵn sàng hoặc trò chơi đã bắt đầu."
+                        }
+                        None => "❌ Phòng chơi đã đóng.",
+                    };
+
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::Message(
+                                CreateInteractionResponseMessage::new()
+                                    .content(content)
+                                    .ephemeral(true),
+                            ),
+                        )
+                        .await;
+                    return;
+                }
+
+                if custom_id == "lobby_start" {
+                    let handle = self
+                        .data
+                        .room_registry
+                        .read()
+                        .await
+                        .get(&component.channel_id)
+                        .cloned();
+
+                    let host_id = match handle {
+                        Some(handle) => {
+                            let (tx, rx) = oneshot::channel();
+                            let _ = handle.sender.send(RoomEvent::StatusRequest { reply: tx });
+                            rx.await.ok().map(|snapshot| snapshot.host_id)
+                        }
+                        None => None,
+                    };
+
+                    let response = match host_id {
+                        Some(host_id) if host_id == component.user.id => {
+                            CreateInteractionResponseMessage::new()
+                                .content("🎮 Chọn cách phân vai trò:")
+                                .components(vec![start_options_row()])
+                        }
+                        Some(_) => CreateInteractionResponseMessage::new()
+                            .content("❌ Chỉ Host của phòng mới có thể bắt đầu trò chơi."),
+                        None => CreateInteractionResponseMessage::new()
+                            .content("❌ Phòng chơi đã đóng."),
+                    };
+
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::Message(response.ephemeral(true)),
+                        )
+                        .await;
+                    return;
+                }
+
+                if custom_id == "settings_edit"
+                    || custom_id == "settings_toggle_balance"
+                    || custom_id == "settings_toggle_ready"
+                    || custom_id == "settings_afk_threshold"
+                    || custom_id == "settings_toggle_mayor"
+                    || custom_id == "settings_toggle_classic"
+                    || custom_id == "settings_banned_roles"
+                {
+                    if !is_admin(component.member.as_ref()) {
+                        let _ = component
+                            .create_response(
+                                &ctx.http,
+                                CreateInteractionResponse::Message(
+                                    CreateInteractionResponseMessage::new()
+                                        .content(
+                                            "❌ Chỉ quản trị viên server mới được đổi cài đặt.",
+                                        )
+                                        .ephemeral(true),
+                                ),
+                            )
+                            .await;
+                        return;
+                    }
+
+                    let mut settings = match self.data.settings.get(guild_id).await {
+                        Ok(settings) => settings,
+                        Err(e) => {
+                            tracing::error!("Lỗi tải cài đặt server {}: {:?}", guild_id, e);
+                            return;
+                        }
+                    };
+
+                    let response = if custom_id == "settings_edit" {
+                        CreateInteractionResponse::Modal(settings_modal(&settings))
+                    } else {
+                        match custom_id.as_str() {
+                            "settings_toggle_ready" => {
+                                settings.ready_check = !settings.ready_check;
+                            }
+                            "settings_toggle_mayor" => {
+                                settings.mayor_election = !settings.mayor_election;
+                            }
+                            "settings_toggle_classic" => {
+                                settings.classic_day = !settings.classic_day;
+                            }
+                            "settings_afk_threshold" => {
+                                if let ComponentInteractionDataKind::StringSelect { values } =
+                                    &component.data.kind
+                                {
+                                    if let Some(threshold) =
+                                        values.first().and_then(|v| v.parse::<u32>().ok())
+                                    {
+                                        settings.afk_threshold = threshold.min(MAX_AFK_THRESHOLD);
+                                    }
+                                }
+                            }
+                            "settings_banned_roles" => {
+                                if let ComponentInteractionDataKind::StringSelect { values } =
+                                    &component.data.kind
+                                {
+                                    let banned: Vec<RoleId> = values
+                                        .iter()
+                                        .filter_map(|v| v.parse::<u8>().ok())
+                                        .filter_map(RoleId::from_u8)
+                                        .collect();
+                                    if let Err(e) = validate_banned_roles(&banned) {
+                                        let _ = component
+                                            .create_response(
+                                                &ctx.http,
+                                                CreateInteractionResponse::Message(
+                                                    CreateInteractionResponseMessage::new()
+                                                        .content(format!("❌ {}", e))
+                                                        .ephemeral(true),
+                                                ),
+                                            )
+                                            .await;
+                                        return;
+                                    }
+                                    settings.banned_roles = banned;
+                                }
+                            }
+                            _ => settings.balanced_shuffle = !settings.balanced_shuffle,
+                        }
+                        if let Err(e) = self.data.settings.save(&settings).await {
+                            tracing::error!("Lỗi lưu cài đặt server {}: {:?}", guild_id, e);
+                            return;
+                        }
+                        CreateInteractionResponse::UpdateMessage(
+                            CreateInteractionResponseMessage::new()
+                                .embed(settings_embed(&settings))
+                                .components(settings_components(&settings)),
+                        )
+                    };
+
+                    let _ = component.create_response(&ctx.http, response).await;
+                    return;
+                }
+
+                if custom_id == "history_select" {
+                    let ComponentInteractionDataKind::StringSelect { values } =
+                        &component.data.kind
+                    else {
+                        return;
+                    };
+
+                    let record = match values.first().map(ObjectId::parse_str) {
+                        Some(Ok(id)) => self.data.history.get(guild_id, id).await,
+                        _ => Ok(None),
+                    };
+
+                    let response = match record {
+                        Ok(Some(record)) => match record_attachments(&record) {
+                            Ok(files) => CreateInteractionResponseMessage::new()
+                                .content(format!(
+                                    "📜 Biên bản ván chơi kết thúc <t:{}:f>",
+                                    record.finished_at / 1000
+                                ))
+                                .files(files),
+                            Err(e) => {
+                                tracing::error!("Lỗi xuất biên bản ván chơi: {:?}", e);
+                                CreateInteractionResponseMessage::new()
+                                    .content("❌ Không thể xuất biên bản ván chơi này.")
+                            }
+                        },
+                        Ok(None) => CreateInteractionResponseMessage::new()
+                            .content("❌ Không tìm thấy ván chơi này."),
+                        Err(e) => {
+                            tracing::error!("Lỗi tải biên bản server {}: {:?}", guild_id, e);
+                            CreateInteractionResponseMessage::new()
+                                .content("❌ Lỗi khi tải biên bản, vui lòng thử lại sau.")
+                        }
+                    };
+
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::Message(response.ephemeral(true)),
+                        )
+                        .await;
+                    return;
+                }
+
                 if custom_id == "start_default" {
                     if let Err(e) = component.defer_ephemeral(&ctx.http).await {
                         tracing::error!("Lỗi defer: {:?}", e);
@@ -773,13 +1556,44 @@ impl EventHandler for Handler {
                         &ctx,
                         InteractionWrapper::Component(component),
                         &self.data,
-                        guild_id,
                         None,
+                        false,
+                    )
+                    .await
+                    {
+                        tracing::error!("start_game error: {:?}", e);
+                    }
+                } else if let Some(encoded) = custom_id.strip_prefix("start_confirm:") {
+                    let roles = match decode_roles(encoded) {
+                        Some(roles) => roles,
+                        None => return,
+                    };
+                    if let Err(e) = component.defer(&ctx.http).await {
+                        tracing::error!("Lỗi defer: {:?}", e);
+                        return;
+                    }
+                    if let Err(e) = handle_start_game(
+                        &ctx,
+                        InteractionWrapper::Component(component),
+                        &self.data,
+                        Some(roles),
+                        true,
                     )
                     .await
                     {
                         tracing::error!("start_game error: {:?}", e);
                     }
+                } else if custom_id == "start_cancel" {
+                    let _ = component
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::UpdateMessage(
+                                CreateInteractionResponseMessage::new()
+                                    .content("🚫 Đã huỷ bắt đầu trò chơi.")
+                                    .components(vec![]),
+                            ),
+                        )
+                        .await;
                 } else if custom_id == "start_custom_json" {
                     let modal = CreateModal::new("modal_start_json", "Tuỳ chỉnh vai trò (JSON)")
                         .components(vec![CreateActionRow::InputText(
@@ -817,6 +1631,30 @@ impl EventHandler for Handler {
                     None => return,
                 };
 
+                if custom_id == "modal_settings" {
+                    if let Err(e) = modal.defer_ephemeral(&ctx.http).await {
+                        tracing::error!("Lỗi defer modal: {:?}", e);
+                        return;
+                    }
+
+                    let response = if !is_admin(modal.member.as_ref()) {
+                        EditInteractionResponse::new()
+                            .content("❌ Chỉ quản trị viên server mới được đổi cài đặt.")
+                    } else {
+                        match update_server_settings(&self.data, guild_id, &modal.data.components)
+                            .await
+                        {
+                            Ok(settings) => EditInteractionResponse::new()
+                                .content("✅ Đã lưu cài đặt!")
+                                .embed(settings_embed(&settings)),
+                            Err(e) => EditInteractionResponse::new().content(format!("❌ {}", e)),
+                        }
+                    };
+
+                    let _ = modal.edit_response(&ctx.http, response).await;
+                    return;
+                }
+
                 if custom_id == "modal_start_name" {
                     let input = match modal
                         .data
@@ -840,8 +1678,8 @@ impl EventHandler for Handler {
                                 &ctx,
                                 InteractionWrapper::Modal(modal),
                                 &self.data,
-                                guild_id,
                                 Some(roles),
+                                false,
                             )
                             .await
                             {
@@ -882,8 +1720,8 @@ impl EventHandler for Handler {
                                 &ctx,
                                 InteractionWrapper::Modal(modal),
                                 &self.data,
-                                guild_id,
                                 Some(roles),
+                                false,
                             )
                             .await
                             {
@@ -910,23 +1748,35 @@ impl EventHandler for Handler {
         println!("{} đã sẵn sàng!", ready.user.name);
         tracing::info!("{} đã sẵn sàng!", ready.user.name);
 
-        let guild_id = std::env::var("GUILD_ID")
-            .map(|id| GuildId::new(id.parse::<u64>().expect("GUILD_ID phải là số")))
-            .expect("Chưa cấu hình GUILD_ID trong .env");
-
-        let mut commands_to_create = Vec::new();
-
-        for cmd_obj in all_commands() {
-            let builder =
-                serenity::all::CreateCommand::new(cmd_obj.name()).description("Lệnh game Ma Sói");
-
-            commands_to_create.push(builder);
-        }
+        let commands_to_create: Vec<_> = all_commands()
+            .iter()
+            .map(|cmd_obj| {
+                serenity::all::CreateCommand::new(cmd_obj.name())
+                    .description(cmd_obj.description())
+                    .set_options(cmd_obj.options())
+            })
+            .collect();
+
+        // Mặc định đăng ký toàn cục; đặt GUILD_ID khi phát triển để lệnh cập nhật ngay trong một server.
+        let result = match std::env::var("GUILD_ID") {
+            Ok(raw) => match raw.trim().parse::<u64>() {
+                Ok(id) => GuildId::new(id)
+                    .set_commands(&ctx.http, commands_to_create)
+                    .await
+                    .map(|_| format!("server {}", id)),
+                Err(_) => {
+                    tracing::error!("GUILD_ID `{}` không hợp lệ, bỏ qua đăng ký lệnh", raw);
+                    return;
+                }
+            },
+            Err(_) => Command::set_global_commands(&ctx.http, commands_to_create)
+                .await
+                .map(|_| "toàn cục".to_string()),
+        };
 
-        if let Err(e) = guild_id.set_commands(&ctx.http, commands_to_create).await {
-            tracing::error!("Lỗi khi đăng ký lệnh: {:?}", e);
-        } else {
-            tracing::info!("Đã đăng ký toàn bộ các lệnh thành công!");
+        match result {
+            Ok(scope) => tracing::info!("Đã đăng ký toàn bộ các lệnh ({}) thành công!", scope),
+            Err(e) => tracing::error!("Lỗi khi đăng ký lệnh: {:?}", e),
         }
     }
 
@@ -937,14 +1787,14 @@ impl EventHandler for Handler {
 
         command_handler(&ctx, &msg, &self.data).await;
         if msg.guild_id.is_none() {
-            let target_guild_id = {
+            let target_channel_id = {
                 let player_registry = self.data.player_registry.read().await;
                 player_registry.get(&msg.author.id).copied()
             };
-            if let Some(guild_id) = target_guild_id {
+            if let Some(channel_id) = target_channel_id {
                 let registry = self.data.room_registry.read().await;
 
-                if let Some(handle) = registry.get(&guild_id) {
+                if let Some(handle) = registry.get(&channel_id) {
                     let _ = handle.sender.send(RoomEvent::WolfChat {
                         sender_id: msg.author.id,
                         sender_name: msg.author.name.clone(),
@@ -970,9 +1820,9 @@ impl EventHandler for Handler {
                     });
 
                     tracing::info!(
-                        "Đã chuyển tin nhắn DM của {} vào phòng {}",
+                        "Đã chuyển tin nhắn DM của {} vào phòng ở kênh {}",
                         msg.author.name,
-                        guild_id
+                        channel_id
                     );
                 }
             }
@@ -1008,11 +1858,11 @@ fn parse_modal_owner(custom_id: &str) -> Option<(&str, String)> {
 
 async fn get_room_handle(
     data: &BotData,
-    guild_id: GuildId,
+    channel_id: ChannelId,
 ) -> Result<crate::game::room::RoomHandle, String> {
     let registry = data.room_registry.read().await;
     registry
-        .get(&guild_id)
+        .get(&channel_id)
         .cloned()
         .ok_or_else(|| "Không tìm thấy phòng chơi.".to_string())
 }
@@ -1065,13 +1915,14 @@ async fn handle_start_game(
     ctx: &Context,
     interaction: InteractionWrapper,
     data: &BotData,
-    guild_id: GuildId,
     roles: Option<HashMap<u8, u8>>,
+    confirmed: bool,
 ) -> anyhow::Result<()> {
     let user_id = interaction.user_id();
+    let channel_id = interaction.channel_id();
 
     let registry = data.room_registry.read().await;
-    let handle = match registry.get(&guild_id) {
+    let handle = match registry.get(&channel_id) {
         Some(h) => h.clone(),
         None => {
             respond_error(ctx, interaction, "Không tìm thấy phòng chơi.".to_string()).await?;
@@ -1103,29 +1954,14 @@ async fn handle_start_game(
     }
 
     if let Some(ref map) = roles {
-        let total: u32 = map.values().map(|v| *v as u32).sum();
-        let players = snapshot.players.len() as u32;
-        if total != players {
-            respond_error(
-                ctx,
-                interaction,
-                format!(
-                    "Tổng số vai trò ({}) phải bằng số người chơi ({}).",
-                    total, players
-                ),
-            )
-            .await?;
+        let check = validate_composition(map, snapshot.players.len());
+        if !check.is_valid() {
+            respond_error(ctx, interaction, check.errors.join("\n")).await?;
             return Ok(());
         }
 
-        let wolves = *map.get(&0).unwrap_or(&0) as u32;
-        if wolves == 0 {
-            respond_error(
-                ctx,
-                interaction,
-                "Phải có ít nhất 1 Sói trong game.".to_string(),
-            )
-            .await?;
+        if !check.warnings.is_empty() && !confirmed {
+            respond_composition_warnings(ctx, interaction, map, &check.warnings).await?;
             return Ok(());
         }
     }
@@ -1164,6 +2000,84 @@ async fn handle_start_game(
     Ok(())
 }
 
+async fn update_server_settings(
+    data: &BotData,
+    guild_id: GuildId,
+    rows: &[ActionRow],
+) -> anyhow::Result<ServerSettings> {
+    let mut settings = data.settings.get(guild_id).await?;
+    apply_settings_modal(&mut settings, rows).map_err(anyhow::Error::msg)?;
+    data.settings.save(&settings).await?;
+    Ok(settings)
+}
+
+async fn respond_composition_warnings(
+    ctx: &Context,
+    interaction: InteractionWrapper,
+    roles: &HashMap<u8, u8>,
+    warnings: &[String],
+) -> serenity::Result<()> {
+    let content = format!(
+        "⚠️ **Đội hình có thể chưa cân bằng:**\n{}\n\nBạn vẫn muốn bắt đầu?",
+        warnings
+            .iter()
+            .map(|w| format!("- {}", w))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+
+    let row = CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("start_confirm:{}", encode_roles(roles)))
+            .label("▶️ Vẫn bắt đầu")
+            .style(ButtonStyle::Success),
+        CreateButton::new("start_cancel")
+            .label("❌ Huỷ")
+            .style(ButtonStyle::Secondary),
+    ]);
+
+    let response = EditInteractionResponse::new()
+        .content(content)
+        .components(vec![row]);
+
+    match interaction {
+        InteractionWrapper::Component(component) => component
+            .edit_response(&ctx.http, response)
+            .await
+            .map(|_| ()),
+        InteractionWrapper::Modal(modal) => {
+            modal.edit_response(&ctx.http, response).await.map(|_| ())
+        }
+    }
+}
+
+/// Mã hoá đội hình thành chuỗi ngắn (mỗi vai trò 2 ký tự base36) để nhét vào custom_id.
+fn encode_roles(roles: &HashMap<u8, u8>) -> String {
+    let mut ids: Vec<&u8> = roles.keys().collect();
+    ids.sort();
+    ids.into_iter()
+        .filter_map(|id| {
+            let role = char::from_digit(*id as u32, 36)?;
+            let count = char::from_digit(roles[id] as u32, 36)?;
+            Some(format!("{}{}", role, count))
+        })
+        .collect()
+}
+
+fn decode_roles(encoded: &str) -> Option<HashMap<u8, u8>> {
+    let chars: Vec<char> = encoded.chars().collect();
+    if chars.is_empty() || !chars.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let mut roles = HashMap::new();
+    for pair in chars.chunks(2) {
+        let role = pair[0].to_digit(36)? as u8;
+        let count = pair[1].to_digit(36)? as u8;
+        roles.insert(role, count);
+    }
+    Some(roles)
+}
+
 async fn respond_success(
     ctx: &Context,
     interaction: InteractionWrapper,
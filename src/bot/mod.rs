@@ -8,7 +8,10 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::bot::handler::Handler;
-use crate::game::room::PlayerRegistry;
+use crate::db::{
+    HistoryRepository, RatingRepository, RoomRepository, SettingsRepository, StatsRepository,
+};
+use crate::game::room::{resume_rooms, PlayerRegistry};
 use crate::game::RoomRegistry;
 use crate::types::data::RolesData;
 
@@ -17,6 +20,11 @@ pub struct BotData {
     pub db: Database,
     pub roles_json: RolesData,
     pub player_registry: PlayerRegistry,
+    pub settings: SettingsRepository,
+    pub room_store: RoomRepository,
+    pub stats: StatsRepository,
+    pub ratings: RatingRepository,
+    pub history: HistoryRepository,
 }
 
 pub async fn start_bot(token: &str, db: Database) -> anyhow::Result<()> {
@@ -34,11 +42,16 @@ pub async fn start_bot(token: &str, db: Database) -> anyhow::Result<()> {
     let data = Arc::new(BotData {
         room_registry: rooms.clone(),
         player_registry: players.clone(),
+        settings: SettingsRepository::new(&db),
+        room_store: RoomRepository::new(&db),
+        stats: StatsRepository::new(&db),
+        ratings: RatingRepository::new(&db),
+        history: HistoryRepository::new(&db),
         db,
         roles_json,
     });
 
-    let handler = Handler { data };
+    let handler = Handler { data: data.clone() };
 
     let intents = GatewayIntents::GUILDS
         | GatewayIntents::GUILD_MESSAGES
@@ -49,6 +62,12 @@ pub async fn start_bot(token: &str, db: Database) -> anyhow::Result<()> {
         .event_handler(handler)
         .await?;
 
+    match resume_rooms(client.http.clone(), &data).await {
+        Ok(0) => {}
+        Ok(count) => tracing::info!("Đã khôi phục {} phòng chơi đang dở", count),
+        Err(e) => tracing::error!("Lỗi khôi phục phòng chơi: {:?}", e),
+    }
+
     client.start().await?;
 
     Ok(())
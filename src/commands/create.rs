@@ -1,7 +1,9 @@
 use crate::bot::BotData;
 use crate::commands::{CommandFuture, SlashCommand};
-use crate::game::room::spawn_room;
-use crate::game::RoomEvent;
+use crate::constants::MAX_ROOMS_PER_GUILD;
+use crate::game::lobby::{lobby_components, lobby_embed, LobbyView};
+use crate::game::room::{guild_room_channels, spawn_room};
+use crate::game::{RoomEvent, RoomSettings, RoomStatus};
 use serenity::all::*;
 use std::sync::Arc;
 
@@ -12,6 +14,10 @@ impl SlashCommand for CreateCommand {
         "masoi-create"
     }
 
+    fn description(&self) -> &'static str {
+        "Tạo phòng chơi Ma Sói mới"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
             let guild_id = match cmd.guild_id {
@@ -30,34 +36,65 @@ impl SlashCommand for CreateCommand {
                 }
             };
 
-            let mut registry = data.room_registry.write().await;
+            let host_id = cmd.user.id;
+            let channel_id = cmd.channel_id;
 
-            if let Some(existing_handle) = registry.get(&guild_id) {
-                if !existing_handle.sender.is_closed() {
-                    cmd.create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new()
-                                .content("❌ Đã có một phòng chơi đang hoạt động trong server này!")
-                                .ephemeral(true),
-                        ),
-                    )
-                    .await?;
-                    return Ok(());
-                } else {
-                }
+            if let Some(playing_channel_id) = data.player_registry.read().await.get(&host_id) {
+                cmd.create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(format!(
+                                "❌ Bạn đang ở trong một phòng chơi khác (<#{}>), hãy rời phòng đó trước.",
+                                playing_channel_id
+                            ))
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+                return Ok(());
             }
 
-            let host_id = cmd.user.id;
-            let channel_id = cmd.channel_id;
+            // Tải cài đặt trước khi khoá registry để không giữ khoá trong lúc chờ MongoDB.
+            let settings: RoomSettings = match data.settings.get(guild_id).await {
+                Ok(saved) => saved.into(),
+                Err(e) => {
+                    tracing::error!("Lỗi tải cài đặt server {}: {:?}", guild_id, e);
+                    RoomSettings::default()
+                }
+            };
 
-            let new_handle = spawn_room(
-                guild_id,
-                host_id,
-                channel_id,
-                &ctx.clone(),
-                data.roles_json.clone(),
-            );
+            let mut registry = data.room_registry.write().await;
+
+            let error = if registry
+                .get(&channel_id)
+                .is_some_and(|handle| !handle.sender.is_closed())
+            {
+                Some("❌ Đã có một phòng chơi đang hoạt động trong kênh này!".to_string())
+            } else if guild_room_channels(&registry, guild_id).len() >= MAX_ROOMS_PER_GUILD {
+                Some(format!(
+                    "❌ Server đã có {} phòng chơi cùng lúc, hãy đợi một phòng kết thúc.",
+                    MAX_ROOMS_PER_GUILD
+                ))
+            } else {
+                None
+            };
+
+            if let Some(error) = error {
+                cmd.create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(error)
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+                return Ok(());
+            }
+
+            let new_handle =
+                spawn_room(guild_id, host_id, channel_id, settings, &ctx.clone(), &data);
 
             let (tx, rx) = tokio::sync::oneshot::channel();
 
@@ -73,32 +110,47 @@ impl SlashCommand for CreateCommand {
 
             let _ = rx.await;
 
-            registry.insert(guild_id, new_handle);
+            let sender = new_handle.sender.clone();
+            registry.insert(channel_id, new_handle);
+            drop(registry);
 
             let mut player_registry = data.player_registry.write().await;
-            player_registry.insert(host_id, guild_id);
+            player_registry.insert(host_id, channel_id);
             drop(player_registry);
 
-            let embed = CreateEmbed::new()
-                .color(0x3498db)
-                .title("🎮 PHÒNG CHƠI MA SÓI MỚI")
-                .description("```🔌 Phòng đã được tạo thành công!```")
-                .field("👑 Chủ Phòng", format!("<@{}>", host_id), true)
-                .field("👥 Số Người Chơi", "1/36", true)
-                .field("⌛ Trạng Thái", "Đang chờ", true)
-                .footer(CreateEmbedFooter::new(
-                    "💡 Sử dụng /masoi-join để tham gia phòng",
-                ))
-                .timestamp(Timestamp::now());
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            let _ = sender.send(RoomEvent::StatusRequest { reply: tx });
+            let players = rx
+                .await
+                .map(|snapshot| snapshot.players)
+                .unwrap_or_default();
+            let view = LobbyView {
+                host_id,
+                players: &players,
+                status: &RoomStatus::Waiting,
+                ready: None,
+                auto_start_at: None,
+            };
 
             cmd.create_response(
                 &ctx.http,
                 CreateInteractionResponse::Message(
-                    CreateInteractionResponseMessage::new().add_embed(embed),
+                    CreateInteractionResponseMessage::new()
+                        .add_embed(lobby_embed(&view))
+                        .components(lobby_components(&view)),
                 ),
             )
             .await?;
 
+            match cmd.get_response(&ctx.http).await {
+                Ok(message) => {
+                    let _ = sender.send(RoomEvent::SetLobbyMessage {
+                        message_id: message.id,
+                    });
+                }
+                Err(e) => tracing::error!("Không lấy được tin nhắn phòng chờ: {:?}", e),
+            }
+
             Ok(())
         })
     }
@@ -11,6 +11,10 @@ impl SlashCommand for HuongDanCommand {
         "huongdan"
     }
 
+    fn description(&self) -> &'static str {
+        "Xem hướng dẫn cách chơi Ma Sói"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, _data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
             let owner_id = cmd.user.id.to_string();
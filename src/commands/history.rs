@@ -0,0 +1,129 @@
+use crate::bot::BotData;
+use crate::commands::{CommandFuture, SlashCommand};
+use crate::db::GameRecord;
+use crate::game::export::{composition_summary, format_time, record_json, record_markdown};
+use serenity::all::*;
+use std::sync::Arc;
+
+pub struct HistoryCommand;
+
+const HISTORY_SIZE: i64 = 10;
+
+impl SlashCommand for HistoryCommand {
+    fn name(&self) -> &'static str {
+        "masoi-history"
+    }
+
+    fn description(&self) -> &'static str {
+        "Xem lại các ván đã kết thúc và tải biên bản"
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            let guild_id = match cmd.guild_id {
+                Some(id) => id,
+                None => {
+                    cmd.create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(
+                            CreateInteractionResponseMessage::new()
+                                .content("❌ Lệnh này chỉ sử dụng được trong server.")
+                                .ephemeral(true),
+                        ),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            cmd.defer(&ctx.http).await?;
+
+            let records = data.history.recent(guild_id, HISTORY_SIZE).await?;
+
+            if records.is_empty() {
+                let embed = CreateEmbed::new()
+                    .color(0x95a5a6)
+                    .title("📚 LỊCH SỬ VÁN CHƠI")
+                    .description("```📭 Server chưa có ván Ma Sói nào kết thúc.```");
+                cmd.edit_response(&ctx.http, EditInteractionResponse::new().add_embed(embed))
+                    .await?;
+                return Ok(());
+            }
+
+            let description = records
+                .iter()
+                .enumerate()
+                .map(|(index, record)| {
+                    format!(
+                        "`#{}` <t:{}:f> — 👥 **{}** người — 🏆 **{}**\n> {}",
+                        index + 1,
+                        record.finished_at / 1000,
+                        record.players.len(),
+                        record.winner.name(),
+                        composition_summary(&record.players)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            let embed = CreateEmbed::new()
+                .color(0x8e44ad)
+                .title("📚 LỊCH SỬ VÁN CHƠI")
+                .description(description)
+                .footer(CreateEmbedFooter::new(
+                    "💡 Chọn một ván bên dưới để tải biên bản (JSON + Markdown)",
+                ))
+                .timestamp(Timestamp::now());
+
+            cmd.edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .add_embed(embed)
+                    .components(vec![history_menu(&records)]),
+            )
+            .await?;
+
+            Ok(())
+        })
+    }
+}
+
+fn history_menu(records: &[GameRecord]) -> CreateActionRow {
+    let options = records
+        .iter()
+        .enumerate()
+        .filter_map(|(index, record)| {
+            let id = record.id?;
+            Some(
+                CreateSelectMenuOption::new(
+                    format!("#{} · {}", index + 1, format_time(record.finished_at)),
+                    id.to_hex(),
+                )
+                .description(format!(
+                    "{} người · {} thắng",
+                    record.players.len(),
+                    record.winner.name()
+                ))
+                .emoji('📜'),
+            )
+        })
+        .collect();
+
+    CreateActionRow::SelectMenu(
+        CreateSelectMenu::new("history_select", CreateSelectMenuKind::String { options })
+            .placeholder("Chọn ván để xem biên bản"),
+    )
+}
+
+/// Biên bản của một ván dưới dạng hai file đính kèm: JSON và Markdown.
+pub fn record_attachments(record: &GameRecord) -> serde_json::Result<Vec<CreateAttachment>> {
+    let file_name = record
+        .id
+        .map(|id| format!("masoi-{}", id.to_hex()))
+        .unwrap_or_else(|| format!("masoi-{}", record.finished_at));
+
+    Ok(vec![
+        CreateAttachment::bytes(record_json(record)?, format!("{}.json", file_name)),
+        CreateAttachment::bytes(record_markdown(record), format!("{}.md", file_name)),
+    ])
+}
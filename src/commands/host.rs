@@ -0,0 +1,162 @@
+use crate::bot::BotData;
+use crate::commands::settings::is_admin;
+use crate::commands::{CommandFuture, SlashCommand};
+use crate::game::{HostActionResult, RoomEvent};
+use serenity::all::*;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+pub struct HostCommand;
+
+impl SlashCommand for HostCommand {
+    fn name(&self) -> &'static str {
+        "masoi-host"
+    }
+
+    fn description(&self) -> &'static str {
+        "Công cụ quản lý phòng cho chủ phòng và quản trị viên"
+    }
+
+    fn options(&self) -> Vec<CreateCommandOption> {
+        let user_sub = |name: &str, description: &str, user_description: &str| {
+            CreateCommandOption::new(CommandOptionType::SubCommand, name, description)
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::User, "user", user_description)
+                        .required(true),
+                )
+        };
+
+        vec![
+            user_sub(
+                "kick",
+                "Mời một người chơi ra khỏi phòng đang chờ",
+                "Người chơi cần mời ra",
+            ),
+            user_sub(
+                "transfer",
+                "Chuyển quyền chủ phòng cho người chơi khác",
+                "Chủ phòng mới",
+            ),
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "pause",
+                "Tạm dừng hoặc tiếp tục các bộ đếm giờ",
+            ),
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "skip",
+                "Kết thúc ngay phase hiện tại",
+            ),
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "abort",
+                "Huỷ ván chơi và đóng phòng",
+            ),
+        ]
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            if cmd.guild_id.is_none() {
+                return respond(&ctx, &cmd, "Lệnh này chỉ dùng trong Server.", true).await;
+            }
+
+            let Some(sub) = cmd.data.options.first() else {
+                return Ok(());
+            };
+            let target = match &sub.value {
+                CommandDataOptionValue::SubCommand(options) => {
+                    options.iter().find_map(|opt| match opt.value {
+                        CommandDataOptionValue::User(id) => Some(id),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            };
+
+            // Chỉ điều khiển phòng trong kênh hiện tại, để quyền quản trị không vươn sang server khác.
+            let room_handle = data
+                .room_registry
+                .read()
+                .await
+                .get(&cmd.channel_id)
+                .cloned();
+            let Some(room_handle) = room_handle else {
+                return respond(
+                    &ctx,
+                    &cmd,
+                    "❌ Không có phòng chơi nào trong kênh này.",
+                    true,
+                )
+                .await;
+            };
+
+            let (stx, srx) = oneshot::channel();
+            let _ = room_handle
+                .sender
+                .send(RoomEvent::StatusRequest { reply: stx });
+            let Ok(snapshot) = srx.await else {
+                return respond(&ctx, &cmd, "❌ Phòng chơi đã đóng.", true).await;
+            };
+
+            if cmd.user.id != snapshot.host_id && !is_admin(cmd.member.as_deref()) {
+                return respond(
+                    &ctx,
+                    &cmd,
+                    "❌ Chỉ chủ phòng hoặc quản trị viên server mới dùng được lệnh này.",
+                    true,
+                )
+                .await;
+            }
+
+            let (tx, rx) = oneshot::channel();
+            let event = match (sub.name.as_str(), target) {
+                ("kick", Some(target)) => RoomEvent::KickPlayer { target, reply: tx },
+                ("transfer", Some(target)) => RoomEvent::TransferHost { target, reply: tx },
+                ("pause", _) => RoomEvent::TogglePause { reply: tx },
+                ("skip", _) => RoomEvent::SkipPhase { reply: tx },
+                ("abort", _) => {
+                    let _ = room_handle.sender.send(RoomEvent::EndGame);
+                    return respond(
+                        &ctx,
+                        &cmd,
+                        &format!("🛑 Ván chơi đã bị <@{}> huỷ.", cmd.user.id),
+                        false,
+                    )
+                    .await;
+                }
+                _ => return respond(&ctx, &cmd, "❌ Thiếu người chơi mục tiêu.", true).await,
+            };
+
+            if room_handle.sender.send(event).is_err() {
+                return respond(&ctx, &cmd, "❌ Phòng chơi đã đóng.", true).await;
+            }
+
+            match rx.await {
+                Ok(HostActionResult::Done(message)) => respond(&ctx, &cmd, &message, false).await,
+                Ok(HostActionResult::Rejected(reason)) => {
+                    respond(&ctx, &cmd, &format!("❌ {}", reason), true).await
+                }
+                Err(_) => respond(&ctx, &cmd, "❌ Lỗi kết nối tới phòng chơi.", true).await,
+            }
+        })
+    }
+}
+
+async fn respond(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    content: &str,
+    ephemeral: bool,
+) -> anyhow::Result<()> {
+    cmd.create_response(
+        &ctx.http,
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(ephemeral),
+        ),
+    )
+    .await?;
+    Ok(())
+}
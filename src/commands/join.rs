@@ -1,5 +1,6 @@
 use crate::bot::BotData;
 use crate::commands::{CommandFuture, SlashCommand};
+use crate::game::room::guild_room_channels;
 use crate::game::{JoinResult, RoomEvent};
 use serenity::all::*;
 use std::sync::Arc;
@@ -12,6 +13,10 @@ impl SlashCommand for JoinCommand {
         "masoi-join"
     }
 
+    fn description(&self) -> &'static str {
+        "Tham gia phòng chơi Ma Sói của server"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
             let guild_id = match cmd.guild_id {
@@ -30,153 +35,106 @@ impl SlashCommand for JoinCommand {
                 }
             };
 
-            let registry = data.room_registry.read().await;
-            let room_handle = match registry.get(&guild_id) {
-                Some(handle) => handle.clone(),
-                None => {
-                    cmd.create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new()
-                                .content("❌ Không có trò chơi ma sói nào đang chờ trong server.")
-                                .ephemeral(true),
-                        ),
-                    )
-                    .await?;
-                    return Ok(());
-                }
-            };
+            let (content, ephemeral) = join_room(&data, guild_id, cmd.channel_id, &cmd.user).await;
 
-            drop(registry);
+            cmd.create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .ephemeral(ephemeral),
+                ),
+            )
+            .await?;
 
-            let player_registry = data.player_registry.read().await;
+            Ok(())
+        })
+    }
+}
 
-            if let Some(playing_guild_id) = player_registry.get(&cmd.user.id) {
-                let msg = format!(
-                    "❌ Bạn đang tham gia một ván game ở Server khác (ID: {}) rồi!\nHãy hoàn thành hoặc rời ván đó trước.",
-                    playing_guild_id
-                );
-                cmd.create_response(
-                    &ctx.http,
-                    CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new()
-                            .content(msg)
-                            .ephemeral(true),
-                    ),
+/// Cho người chơi vào phòng của kênh, trả về nội dung phản hồi và có cần ẩn (ephemeral) hay không.
+pub async fn join_room(
+    data: &BotData,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    user: &User,
+) -> (String, bool) {
+    let registry = data.room_registry.read().await;
+    let room_handle = match registry.get(&channel_id) {
+        Some(handle) => handle.clone(),
+        None => {
+            let channels = guild_room_channels(&registry, guild_id);
+            let content = if channels.is_empty() {
+                "❌ Không có trò chơi ma sói nào đang chờ trong server.".to_string()
+            } else {
+                format!(
+                    "❌ Kênh này không có phòng chơi. Các phòng đang mở: {}",
+                    channels
+                        .iter()
+                        .map(|c| format!("<#{}>", c))
+                        .collect::<Vec<_>>()
+                        .join(", ")
                 )
-                .await?;
-                return Ok(());
-            }
-            drop(player_registry);
-
-            let avatar_url = cmd.user.face();
-
-            let (tx, rx) = oneshot::channel();
+            };
+            return (content, true);
+        }
+    };
+    drop(registry);
 
-            let send_result = room_handle.sender.send(RoomEvent::JoinRequest {
-                user_id: cmd.user.id,
-                name: cmd.user.name.clone(),
-                avatar_url: avatar_url,
-                channel_id: cmd.channel_id,
-                reply: tx,
-            });
+    if let Some(playing_channel_id) = data.player_registry.read().await.get(&user.id) {
+        return (
+            format!(
+                "❌ Bạn đang tham gia một ván game ở kênh <#{}> rồi!\nHãy hoàn thành hoặc rời ván đó trước.",
+                playing_channel_id
+            ),
+            true,
+        );
+    }
 
-            if send_result.is_err() {
-                cmd.create_response(
-                    &ctx.http,
-                    CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new()
-                            .content("❌ Phòng chơi đã bị đóng đột ngột.")
-                            .ephemeral(true),
-                    ),
-                )
-                .await?;
-                return Ok(());
-            }
+    let (tx, rx) = oneshot::channel();
 
-            match rx.await {
-                Ok(result) => match result {
-                    JoinResult::Success(count) => {
-                        let mut player_registry = data.player_registry.write().await;
-                        player_registry.insert(cmd.user.id, guild_id);
-                        drop(player_registry);
+    let send_result = room_handle.sender.send(RoomEvent::JoinRequest {
+        user_id: user.id,
+        name: user.name.clone(),
+        avatar_url: user.face(),
+        channel_id,
+        reply: tx,
+    });
 
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content(format!(
-                                        "✅ <@{}> đã tham gia phòng! Hiện có {} người",
-                                        cmd.user.id, count
-                                    ))
-                                    .ephemeral(false),
-                            ),
-                        )
-                        .await?;
-                    }
-                    JoinResult::RoomFull => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("❌ Đã quá giới hạn số lượng người tham gia.")
-                                    .ephemeral(true),
-                            ),
-                        )
-                        .await?;
-                    }
-                    JoinResult::AlreadyJoined => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("⚠️ Bạn đã tham gia trò chơi rồi.")
-                                    .ephemeral(true),
-                            ),
-                        )
-                        .await?;
-                    }
-                    JoinResult::GameStarted => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("⚠️ Trò chơi đã bắt đầu, không thể tham gia.")
-                                    .ephemeral(true),
-                            ),
-                        )
-                        .await?;
-                    }
-                    JoinResult::WrongChannel(true_channel_id, host_id) => {
-                        let msg = format!(
-                            "⚠️ Trò chơi bắt đầu ở kênh <#{}>, hãy vào kênh để tham gia.\nNếu không thấy, liên hệ <@{}>",
-                            true_channel_id, host_id
-                        );
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content(msg)
-                                    .ephemeral(true),
-                            ),
-                        )
-                        .await?;
-                    }
-                },
-                Err(_) => {
-                    cmd.create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new()
-                                .content("❌ Lỗi kết nối tới phòng chơi.")
-                                .ephemeral(true),
-                        ),
-                    )
-                    .await?;
-                }
-            }
+    if send_result.is_err() {
+        return ("❌ Phòng chơi đã bị đóng đột ngột.".to_string(), true);
+    }
 
-            Ok(())
-        })
+    match rx.await {
+        Ok(JoinResult::Success(count)) => {
+            data.player_registry
+                .write()
+                .await
+                .insert(user.id, channel_id);
+            (
+                format!(
+                    "✅ <@{}> đã tham gia phòng! Hiện có {} người",
+                    user.id, count
+                ),
+                false,
+            )
+        }
+        Ok(JoinResult::RoomFull) => (
+            "❌ Đã quá giới hạn số lượng người tham gia.".to_string(),
+            true,
+        ),
+        Ok(JoinResult::AlreadyJoined) => ("⚠️ Bạn đã tham gia trò chơi rồi.".to_string(), true),
+        Ok(JoinResult::GameStarted) => (
+            "⚠️ Trò chơi đã bắt đầu, không thể tham gia.".to_string(),
+            true,
+        ),
+        Ok(JoinResult::WrongChannel(true_channel_id, host_id)) => (
+            format!(
+                "⚠️ Trò chơi bắt đầu ở kênh <#{}>, hãy vào kênh để tham gia.\nNếu không thấy, liên hệ <@{}>",
+                true_channel_id, host_id
+            ),
+            true,
+        ),
+        Err(_) => ("❌ Lỗi kết nối tới phòng chơi.".to_string(), true),
     }
 }
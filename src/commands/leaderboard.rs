@@ -0,0 +1,82 @@
+use crate::bot::BotData;
+use crate::commands::{CommandFuture, SlashCommand};
+use serenity::all::*;
+use std::sync::Arc;
+
+pub struct LeaderboardCommand;
+
+const LEADERBOARD_SIZE: usize = 10;
+
+impl SlashCommand for LeaderboardCommand {
+    fn name(&self) -> &'static str {
+        "masoi-leaderboard"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bảng xếp hạng người chơi của server"
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            let guild_id = match cmd.guild_id {
+                Some(id) => id,
+                None => {
+                    cmd.create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(
+                            CreateInteractionResponseMessage::new()
+                                .content("❌ Lệnh này chỉ sử dụng được trong server.")
+                                .ephemeral(true),
+                        ),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            cmd.defer(&ctx.http).await?;
+
+            let entries = data.stats.leaderboard(guild_id, LEADERBOARD_SIZE).await?;
+
+            let description = if entries.is_empty() {
+                "```📭 Server chưa có ván Ma Sói nào kết thúc.```".to_string()
+            } else {
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(|(index, entry)| {
+                        let medal = match index {
+                            0 => "🥇".to_string(),
+                            1 => "🥈".to_string(),
+                            2 => "🥉".to_string(),
+                            _ => format!("`#{}`", index + 1),
+                        };
+                        format!(
+                            "{} <@{}> — **{}** thắng / {} ván ({:.1}%)",
+                            medal,
+                            entry.user_id,
+                            entry.stats.wins,
+                            entry.stats.games,
+                            entry.stats.win_rate()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+
+            let embed = CreateEmbed::new()
+                .color(0xf1c40f)
+                .title("🏆 BẢNG XẾP HẠNG MA SÓI")
+                .description(description)
+                .footer(CreateEmbedFooter::new(
+                    "💡 Sử dụng /masoi-stats để xem thống kê chi tiết",
+                ))
+                .timestamp(Timestamp::now());
+
+            cmd.edit_response(&ctx.http, EditInteractionResponse::new().add_embed(embed))
+                .await?;
+
+            Ok(())
+        })
+    }
+}
@@ -12,147 +12,99 @@ impl SlashCommand for LeaveCommand {
         "masoi-leave"
     }
 
+    fn description(&self) -> &'static str {
+        "Rời khỏi phòng chơi hiện tại"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
-            let guild_id = match cmd.guild_id {
-                Some(id) => id,
-                None => {
-                    cmd.create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new()
-                                .content("Lệnh này chỉ dùng trong Server.")
-                                .ephemeral(true),
-                        ),
-                    )
-                    .await?;
-                    return Ok(());
-                }
-            };
-
-            let room_handle = {
-                let registry = data.room_registry.read().await;
-                match registry.get(&guild_id) {
-                    Some(handle) => Some(handle.clone()),
-                    None => None,
-                }
-            };
-
-            let room_handle = match room_handle {
-                Some(handle) => handle,
-                None => {
-                    cmd.create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new()
-                                .content("❌ Không tìm thấy phòng ma sói nào.")
-                                .ephemeral(true),
-                        ),
-                    )
-                    .await?;
-                    return Ok(());
-                }
-            };
-
-            let (tx, rx) = oneshot::channel();
-
-            if room_handle
-                .sender
-                .send(RoomEvent::LeaveRequest {
-                    user_id: cmd.user.id,
-                    reply: tx,
-                })
-                .is_err()
-            {
+            if cmd.guild_id.is_none() {
                 cmd.create_response(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content("❌ Phòng chơi đã đóng cửa.")
+                            .content("Lệnh này chỉ dùng trong Server.")
                             .ephemeral(true),
                     ),
                 )
                 .await?;
-                data.room_registry.write().await.remove(&guild_id);
                 return Ok(());
             }
 
-            match rx.await {
-                Ok(result) => match result {
-                    LeaveResult::Success(count) => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content(format!(
-                                        "✅ Bạn đã rời khỏi phòng. Còn lại {} người.",
-                                        count
-                                    ))
-                                    .ephemeral(false),
-                            ),
-                        )
-                        .await?;
-                    }
-                    LeaveResult::NotJoined => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("⚠️ Bạn chưa tham gia phòng chơi này.")
-                                    .ephemeral(true),
-                            ),
-                        )
-                        .await?;
-                    }
-                    LeaveResult::GameStarted => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("⚠️ Trò chơi đã bắt đầu, không thể rời.")
-                                    .ephemeral(true),
-                            ),
-                        )
-                        .await?;
-                    }
-                    LeaveResult::RoomEmpty => {
-                        cmd.create_response(
-                            &ctx.http,
-                            CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("✅ Bạn đã rời phòng. Phòng trống nên đã bị hủy.")
-                                    .ephemeral(false),
-                            ),
-                        )
-                        .await?;
+            // Ưu tiên phòng người chơi đang tham gia, nếu không có thì lấy phòng của kênh hiện tại.
+            let channel_id = data
+                .player_registry
+                .read()
+                .await
+                .get(&cmd.user.id)
+                .copied()
+                .unwrap_or(cmd.channel_id);
 
-                        let _ = cmd
-                            .channel_id
-                            .say(
-                                &ctx.http,
-                                "🗑️ Không còn ai trong phòng nên phòng Ma Sói đã bị hủy.",
-                            )
-                            .await;
+            let (content, ephemeral) = leave_room(&ctx.http, &data, channel_id, cmd.user.id).await;
 
-                        let mut registry = data.room_registry.write().await;
-                        registry.remove(&guild_id);
-                        tracing::info!("Room {} removed because it is empty", guild_id);
-                    }
-                },
-                Err(_) => {
-                    cmd.create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new()
-                                .content("❌ Lỗi xử lý.")
-                                .ephemeral(true),
-                        ),
-                    )
-                    .await?;
-                }
-            }
+            cmd.create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .ephemeral(ephemeral),
+                ),
+            )
+            .await?;
 
             Ok(())
         })
     }
 }
+
+/// Cho người chơi rời phòng của kênh, trả về nội dung phản hồi và có cần ẩn (ephemeral) hay không.
+pub async fn leave_room(
+    http: &Http,
+    data: &BotData,
+    channel_id: ChannelId,
+    user_id: UserId,
+) -> (String, bool) {
+    let room_handle = match data.room_registry.read().await.get(&channel_id).cloned() {
+        Some(handle) => handle,
+        None => return ("❌ Không tìm thấy phòng ma sói nào.".to_string(), true),
+    };
+
+    let (tx, rx) = oneshot::channel();
+
+    if room_handle
+        .sender
+        .send(RoomEvent::LeaveRequest { user_id, reply: tx })
+        .is_err()
+    {
+        data.room_registry.write().await.remove(&channel_id);
+        return ("❌ Phòng chơi đã đóng cửa.".to_string(), true);
+    }
+
+    match rx.await {
+        Ok(LeaveResult::Success(count)) => (
+            format!("✅ Bạn đã rời khỏi phòng. Còn lại {} người.", count),
+            false,
+        ),
+        Ok(LeaveResult::NotJoined) => ("⚠️ Bạn chưa tham gia phòng chơi này.".to_string(), true),
+        Ok(LeaveResult::GameStarted) => {
+            ("⚠️ Trò chơi đã bắt đầu, không thể rời. Dùng `/masoi-replace` để nhường ghế cho người khác.".to_string(), true)
+        }
+        Ok(LeaveResult::RoomEmpty) => {
+            let _ = channel_id
+                .say(
+                    http,
+                    "🗑️ Không còn ai trong phòng nên phòng Ma Sói đã bị hủy.",
+                )
+                .await;
+
+            data.room_registry.write().await.remove(&channel_id);
+            tracing::info!("Room in channel {} removed because it is empty", channel_id);
+
+            (
+                "✅ Bạn đã rời phòng. Phòng trống nên đã bị hủy.".to_string(),
+                false,
+            )
+        }
+        Err(_) => ("❌ Lỗi xử lý.".to_string(), true),
+    }
+}
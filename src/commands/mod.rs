@@ -1,20 +1,29 @@
 mod create;
 pub mod guide;
-mod join;
-mod leave;
+pub mod history;
+mod host;
+pub mod join;
+mod leaderboard;
+pub mod leave;
 mod ping;
+mod replace;
 pub mod role;
-mod start;
+pub mod settings;
+mod spectate;
+pub mod start;
+mod stats;
 mod status;
 
 use crate::commands::{
-    guide::HuongDanCommand, join::JoinCommand, leave::LeaveCommand, ping::PingCommand,
-    role::RoleCommand, start::StartCommand, status::StatusCommand,
+    guide::HuongDanCommand, history::HistoryCommand, host::HostCommand, join::JoinCommand,
+    leaderboard::LeaderboardCommand, leave::LeaveCommand, ping::PingCommand,
+    replace::ReplaceCommand, role::RoleCommand, settings::SettingsCommand,
+    spectate::SpectateCommand, start::StartCommand, stats::StatsCommand, status::StatusCommand,
 };
 use create::CreateCommand;
 
 use crate::bot::BotData;
-use serenity::all::{CommandInteraction, Context};
+use serenity::all::{CommandInteraction, Context, CreateCommandOption};
 use std::{future::Future, pin::Pin, sync::Arc};
 
 pub type CommandResult = anyhow::Result<()>;
@@ -22,6 +31,12 @@ pub type CommandFuture = Pin<Box<dyn Future<Output = CommandResult> + Send>>;
 
 pub trait SlashCommand: Send + Sync {
     fn name(&self) -> &'static str;
+    /// Mô tả hiển thị trong danh sách lệnh của Discord (tối đa 100 ký tự).
+    fn description(&self) -> &'static str;
+    /// Các tham số của lệnh khi đăng ký với Discord.
+    fn options(&self) -> Vec<CreateCommandOption> {
+        Vec::new()
+    }
     fn run(&self, ctx: Context, command: CommandInteraction, data: Arc<BotData>) -> CommandFuture;
 }
 
@@ -35,5 +50,12 @@ pub fn all_commands() -> Vec<Box<dyn SlashCommand>> {
         Box::new(LeaveCommand),
         Box::new(StatusCommand),
         Box::new(StartCommand),
+        Box::new(SettingsCommand),
+        Box::new(StatsCommand),
+        Box::new(LeaderboardCommand),
+        Box::new(HistoryCommand),
+        Box::new(SpectateCommand),
+        Box::new(HostCommand),
+        Box::new(ReplaceCommand),
     ]
 }
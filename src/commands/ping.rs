@@ -11,6 +11,10 @@ impl SlashCommand for PingCommand {
         "ping"
     }
 
+    fn description(&self) -> &'static str {
+        "Kiểm tra độ trễ của bot"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, _data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
             let start_time = Instant::now();
@@ -0,0 +1,108 @@
+use crate::bot::BotData;
+use crate::commands::{CommandFuture, SlashCommand};
+use crate::game::{HostActionResult, RoomEvent};
+use serenity::all::*;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+pub struct ReplaceCommand;
+
+impl SlashCommand for ReplaceCommand {
+    fn name(&self) -> &'static str {
+        "masoi-replace"
+    }
+
+    fn description(&self) -> &'static str {
+        "Nhường ghế trong ván đang chơi cho người khác vào thay"
+    }
+
+    fn options(&self) -> Vec<CreateCommandOption> {
+        vec![CreateCommandOption::new(
+            CommandOptionType::User,
+            "user",
+            "Người chơi cần thay (chỉ chủ phòng), bỏ trống để nhường ghế của bạn",
+        )]
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            if cmd.guild_id.is_none() {
+                return respond(&ctx, &cmd, "Lệnh này chỉ dùng trong Server.", true).await;
+            }
+
+            let seat = cmd
+                .data
+                .options
+                .iter()
+                .find_map(|opt| match opt.value {
+                    CommandDataOptionValue::User(id) => Some(id),
+                    _ => None,
+                })
+                .unwrap_or(cmd.user.id);
+
+            // Ưu tiên phòng mà người được nhường ghế đang chơi, nếu không có thì lấy phòng của kênh.
+            let room = {
+                let registry = data.room_registry.read().await;
+                let seat_channel = data.player_registry.read().await.get(&seat).copied();
+                seat_channel
+                    .into_iter()
+                    .chain([cmd.channel_id])
+                    .find_map(|c| registry.get(&c).map(|handle| (c, handle.clone())))
+            };
+            let Some((room_channel, room_handle)) = room else {
+                return respond(&ctx, &cmd, "❌ Không tìm thấy phòng chơi.", true).await;
+            };
+
+            let (tx, rx) = oneshot::channel();
+            let event = RoomEvent::OfferSeat {
+                requester: cmd.user.id,
+                seat,
+                reply: tx,
+            };
+            if room_handle.sender.send(event).is_err() {
+                return respond(&ctx, &cmd, "❌ Phòng chơi đã đóng.", true).await;
+            }
+
+            match rx.await {
+                Ok(HostActionResult::Done(message)) => {
+                    // Nút có thể được bấm ở kênh khác với phòng nên phải mang theo kênh của phòng.
+                    let button = CreateButton::new(format!("seat_claim:{}:{}", room_channel, seat))
+                        .label("🔄 Vào thay")
+                        .style(ButtonStyle::Success);
+                    cmd.create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(
+                            CreateInteractionResponseMessage::new()
+                                .content(message)
+                                .components(vec![CreateActionRow::Buttons(vec![button])]),
+                        ),
+                    )
+                    .await?;
+                    Ok(())
+                }
+                Ok(HostActionResult::Rejected(reason)) => {
+                    respond(&ctx, &cmd, &format!("❌ {}", reason), true).await
+                }
+                Err(_) => respond(&ctx, &cmd, "❌ Lỗi kết nối tới phòng chơi.", true).await,
+            }
+        })
+    }
+}
+
+async fn respond(
+    ctx: &Context,
+    cmd: &CommandInteraction,
+    content: &str,
+    ephemeral: bool,
+) -> anyhow::Result<()> {
+    cmd.create_response(
+        &ctx.http,
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(content)
+                .ephemeral(ephemeral),
+        ),
+    )
+    .await?;
+    Ok(())
+}
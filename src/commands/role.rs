@@ -2,6 +2,10 @@ use crate::bot::BotData;
 use crate::{
     commands::{CommandFuture, SlashCommand},
     types::data::RolesData,
+    utils::{
+        embed::create_werewolf_embed,
+        role::{convert_faction_role, RoleId},
+    },
 };
 use serenity::all::*;
 use std::sync::Arc;
@@ -13,10 +17,47 @@ impl SlashCommand for RoleCommand {
         "role"
     }
 
+    fn description(&self) -> &'static str {
+        "Xem thông tin các vai trò trong game"
+    }
+
+    fn options(&self) -> Vec<CreateCommandOption> {
+        let option = CreateCommandOption::new(
+            CommandOptionType::String,
+            "role",
+            "Vai trò muốn xem ngay (bỏ trống để chọn từ menu)",
+        )
+        .required(false);
+
+        let option = (0..=u8::MAX)
+            .map_while(RoleId::from_u8)
+            .filter(|role| *role != RoleId::Dead)
+            .fold(option, |option, role| {
+                option.add_string_choice(role.name(), (role as u8).to_string())
+            });
+
+        vec![option]
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
             let owner_id = cmd.user.id.to_string();
 
+            let selected = cmd.data.options.iter().find_map(|opt| match &opt.value {
+                CommandDataOptionValue::String(id) if opt.name == "role" => Some(id.clone()),
+                _ => None,
+            });
+
+            if let Some(selected_id) = selected {
+                if let Some(message) =
+                    role_info_message(&selected_id, &owner_id, &data.roles_json).await
+                {
+                    cmd.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+                        .await?;
+                    return Ok(());
+                }
+            }
+
             let initial_embed = CreateEmbed::new()
                 .title("🎭 THÔNG TIN VAI TRÒ")
                 .description(
@@ -84,3 +125,37 @@ pub fn get_role_menu_row(owner_id: &str, roles_data: &RolesData) -> CreateAction
 
     CreateActionRow::SelectMenu(menu)
 }
+
+/// Tin nhắn chi tiết một vai trò kèm menu để chọn vai khác, `None` nếu không có vai đó.
+pub async fn role_info_message(
+    selected_id: &str,
+    owner_id: &str,
+    roles_data: &RolesData,
+) -> Option<CreateInteractionResponseMessage> {
+    let role = roles_data.get(selected_id)?;
+
+    let file_name = format!("{}.png", role.e_name.to_lowercase().replace(" ", "_"));
+    let faction_name = convert_faction_role(role.faction);
+
+    let description = format!("{}\n\n**Phe:** {}", role.description, faction_name);
+    let data_embed = match create_werewolf_embed(
+        &file_name,
+        &format!("{} ({})", role.title, role.e_name),
+        &description,
+    )
+    .await
+    {
+        Ok(embed) => embed,
+        Err(e) => {
+            tracing::error!("Lỗi tạo embed vai trò {}: {:?}", selected_id, e);
+            return None;
+        }
+    };
+
+    Some(
+        CreateInteractionResponseMessage::new()
+            .add_embed(data_embed.embed)
+            .add_file(data_embed.attachment)
+            .components(vec![get_role_menu_row(owner_id, roles_data)]),
+    )
+}
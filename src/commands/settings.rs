@@ -0,0 +1,296 @@
+use crate::bot::BotData;
+use crate::commands::{CommandFuture, SlashCommand};
+use crate::constants::{MAX_AFK_THRESHOLD, MAX_PHASE_TIME, MIN_PHASE_TIME};
+use crate::db::ServerSettings;
+use crate::utils::role::bannable_roles;
+use serenity::all::*;
+use std::sync::Arc;
+
+pub struct SettingsCommand;
+
+/// Các ô nhập trong modal: (custom_id, nhãn).
+const TIME_FIELDS: [(&str, &str); 5] = [
+    ("wolf_vote_time", "Thời gian Sói bỏ phiếu (giây)"),
+    ("night_time", "Thời gian ban đêm (giây)"),
+    ("discuss_time", "Thời gian thảo luận (giây)"),
+    ("vote_time", "Thời gian bỏ phiếu treo cổ (giây)"),
+    ("auto_start_time", "Đếm ngược tự bắt đầu (giây, 0 = tắt)"),
+];
+
+impl SlashCommand for SettingsCommand {
+    fn name(&self) -> &'static str {
+        "masoi-settings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Xem và chỉnh cài đặt Ma Sói của server"
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            let guild_id = match cmd.guild_id {
+                Some(id) => id,
+                None => {
+                    cmd.create_response(
+                        &ctx.http,
+                        CreateInteractionResponse::Message(
+                            CreateInteractionResponseMessage::new()
+                                .content("❌ Lệnh này chỉ sử dụng được trong server.")
+                                .ephemeral(true),
+                        ),
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            let settings = data.settings.get(guild_id).await?;
+
+            let mut response = CreateInteractionResponseMessage::new()
+                .add_embed(settings_embed(&settings))
+                .ephemeral(true);
+
+            if is_admin(cmd.member.as_deref()) {
+                response = response.components(settings_components(&settings));
+            }
+
+            cmd.create_response(&ctx.http, CreateInteractionResponse::Message(response))
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Chỉ người có quyền Administrator hoặc Manage Server mới được đổi cài đặt.
+pub fn is_admin(member: Option<&Member>) -> bool {
+    member
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator() || p.manage_guild())
+}
+
+pub fn settings_components(settings: &ServerSettings) -> Vec<CreateActionRow> {
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new("settings_edit")
+            .label("✏️ Chỉnh sửa")
+            .style(ButtonStyle::Primary),
+        CreateButton::new("settings_toggle_balance")
+            .label("⚖️ Bật/Tắt chia vai cân bằng")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new("settings_toggle_ready")
+            .label("✋ Bật/Tắt kiểm tra sẵn sàng")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new("settings_toggle_mayor")
+            .label("🎖️ Bật/Tắt bầu Thị Trưởng")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new("settings_toggle_classic")
+            .label("📜 Bật/Tắt ngày cổ điển")
+            .style(ButtonStyle::Secondary),
+    ]);
+
+    let afk_options = (0..=MAX_AFK_THRESHOLD)
+        .map(|threshold| {
+            let label = if threshold == 0 {
+                "Tắt xử lý AFK".to_string()
+            } else {
+                format!("Cảnh báo sau {} lượt bỏ lỡ", threshold)
+            };
+            CreateSelectMenuOption::new(label, threshold.to_string())
+                .default_selection(threshold == settings.afk_threshold)
+        })
+        .collect();
+    let afk_select = CreateSelectMenu::new(
+        "settings_afk_threshold",
+        CreateSelectMenuKind::String {
+            options: afk_options,
+        },
+    )
+    .placeholder("💤 Ngưỡng AFK");
+
+    let ban_options: Vec<CreateSelectMenuOption> = bannable_roles()
+        .map(|role| {
+            CreateSelectMenuOption::new(role.name(), (role as u8).to_string())
+                .default_selection(settings.banned_roles.contains(&role))
+        })
+        .collect();
+    let ban_count = ban_options.len() as u8;
+    let ban_select = CreateSelectMenu::new(
+        "settings_banned_roles",
+        CreateSelectMenuKind::String {
+            options: ban_options,
+        },
+    )
+    .placeholder("🚫 Vai trò bị cấm khi chia vai tự động")
+    .min_values(0)
+    .max_values(ban_count);
+
+    vec![
+        buttons,
+        CreateActionRow::SelectMenu(afk_select),
+        CreateActionRow::SelectMenu(ban_select),
+    ]
+}
+
+pub fn settings_embed(settings: &ServerSettings) -> CreateEmbed {
+    CreateEmbed::new()
+        .color(0x3498db)
+        .title("⚙️ CÀI ĐẶT MA SÓI CỦA SERVER")
+        .field(
+            "🐺 Sói bỏ phiếu",
+            format!("> {} giây", settings.wolf_vote_time),
+            true,
+        )
+        .field(
+            "🌙 Ban đêm",
+            format!("> {} giây", settings.night_time),
+            true,
+        )
+        .field(
+            "💬 Thảo luận",
+            format!("> {} giây", settings.discuss_time),
+            true,
+        )
+        .field(
+            "🗳️ Bỏ phiếu",
+            format!("> {} giây", settings.vote_time),
+            true,
+        )
+        .field(
+            "⚖️ Chia vai cân bằng",
+            if settings.balanced_shuffle {
+                "> Bật (theo rating)"
+            } else {
+                "> Tắt"
+            },
+            true,
+        )
+        .field(
+            "✋ Kiểm tra sẵn sàng",
+            if settings.ready_check {
+                "> Bật"
+            } else {
+                "> Tắt"
+            },
+            true,
+        )
+        .field(
+            "⏱️ Tự bắt đầu khi đủ người",
+            if settings.auto_start_time > 0 {
+                format!("> Sau {} giây", settings.auto_start_time)
+            } else {
+                "> Tắt".to_string()
+            },
+            true,
+        )
+        .field(
+            "🎖️ Bầu Thị Trưởng",
+            if settings.mayor_election {
+                "> Bật (đầu ngày đầu tiên)"
+            } else {
+                "> Tắt"
+            },
+            true,
+        )
+        .field(
+            "📜 Ngày cổ điển",
+            if settings.classic_day {
+                "> Bật (tố cáo → bào chữa → phán quyết)"
+            } else {
+                "> Tắt"
+            },
+            true,
+        )
+        .field(
+            "💤 Xử lý AFK",
+            if settings.afk_threshold > 0 {
+                format!(
+                    "> Cảnh báo sau {} lượt bỏ lỡ, loại ở lượt tiếp theo",
+                    settings.afk_threshold
+                )
+            } else {
+                "> Tắt".to_string()
+            },
+            true,
+        )
+        .field(
+            "🚫 Vai trò bị cấm",
+            if settings.banned_roles.is_empty() {
+                "> Không có".to_string()
+            } else {
+                let names: Vec<&str> = settings.banned_roles.iter().map(|r| r.name()).collect();
+                format!("> {}", names.join(", "))
+            },
+            false,
+        )
+        .footer(CreateEmbedFooter::new(
+            "💡 Cài đặt mới áp dụng cho các phòng được tạo sau đó",
+        ))
+        .timestamp(Timestamp::now())
+}
+
+pub fn settings_modal(settings: &ServerSettings) -> CreateModal {
+    let values = [
+        settings.wolf_vote_time,
+        settings.night_time,
+        settings.discuss_time,
+        settings.vote_time,
+        settings.auto_start_time,
+    ];
+
+    let rows = TIME_FIELDS
+        .iter()
+        .zip(values)
+        .map(|((id, label), value)| {
+            CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, *label, *id)
+                    .value(value.to_string())
+                    .required(true),
+            )
+        })
+        .collect();
+
+    CreateModal::new("modal_settings", "Cài đặt thời gian").components(rows)
+}
+
+/// Đọc các giá trị từ modal vào `settings`, báo lỗi nếu có ô không hợp lệ.
+pub fn apply_settings_modal(
+    settings: &mut ServerSettings,
+    rows: &[ActionRow],
+) -> Result<(), String> {
+    for component in rows.iter().flat_map(|r| r.components.iter()) {
+        let ActionRowComponent::InputText(text) = component else {
+            continue;
+        };
+
+        let label = TIME_FIELDS
+            .iter()
+            .find(|(id, _)| *id == text.custom_id)
+            .map(|(_, label)| *label)
+            .unwrap_or_default();
+
+        let raw = text.value.as_deref().unwrap_or("").trim();
+        let value: u64 = raw
+            .parse()
+            .map_err(|_| format!("{}: `{}` không phải là số.", label, raw))?;
+
+        // Riêng đếm ngược tự bắt đầu được phép để 0 (tắt).
+        let disabled = text.custom_id == "auto_start_time" && value == 0;
+        if !disabled && !(MIN_PHASE_TIME..=MAX_PHASE_TIME).contains(&value) {
+            return Err(format!(
+                "{}: phải nằm trong khoảng {}-{} giây.",
+                label, MIN_PHASE_TIME, MAX_PHASE_TIME
+            ));
+        }
+
+        match text.custom_id.as_str() {
+            "wolf_vote_time" => settings.wolf_vote_time = value,
+            "night_time" => settings.night_time = value,
+            "discuss_time" => settings.discuss_time = value,
+            "vote_time" => settings.vote_time = value,
+            "auto_start_time" => settings.auto_start_time = value,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
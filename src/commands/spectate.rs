@@ -0,0 +1,104 @@
+use crate::bot::BotData;
+use crate::commands::{CommandFuture, SlashCommand};
+use crate::game::{RoomEvent, SpectateResult};
+use serenity::all::*;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+pub struct SpectateCommand;
+
+impl SlashCommand for SpectateCommand {
+    fn name(&self) -> &'static str {
+        "masoi-spectate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Xem (hoặc thôi xem) ván chơi trong kênh này qua tin nhắn riêng"
+    }
+
+    fn options(&self) -> Vec<CreateCommandOption> {
+        vec![CreateCommandOption::new(
+            CommandOptionType::Boolean,
+            "full",
+            "Xem cả vai trò, chat của Sói và hành động ban đêm",
+        )
+        .required(false)]
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            if cmd.guild_id.is_none() {
+                cmd.create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("Lệnh này chỉ dùng trong Server.")
+                            .ephemeral(true),
+                    ),
+                )
+                .await?;
+                return Ok(());
+            }
+
+            let full_view = cmd
+                .data
+                .options
+                .iter()
+                .find_map(|opt| match opt.value {
+                    CommandDataOptionValue::Boolean(value) if opt.name == "full" => Some(value),
+                    _ => None,
+                })
+                .unwrap_or(false);
+
+            let room_handle = data
+                .room_registry
+                .read()
+                .await
+                .get(&cmd.channel_id)
+                .cloned();
+
+            let (tx, rx) = oneshot::channel();
+            let sent = room_handle.is_some_and(|handle| {
+                handle
+                    .sender
+                    .send(RoomEvent::SpectateRequest {
+                        user_id: cmd.user.id,
+                        full_view,
+                        reply: tx,
+                    })
+                    .is_ok()
+            });
+
+            let content = if !sent {
+                "❌ Kênh này không có phòng Ma Sói nào.".to_string()
+            } else {
+                match rx.await {
+                    Ok(SpectateResult::Joined) if full_view => {
+                        "👁️ Bạn đang xem ván chơi với **toàn bộ thông tin**. Diễn biến sẽ được gửi qua tin nhắn riêng.".to_string()
+                    }
+                    Ok(SpectateResult::Joined) => {
+                        "👀 Bạn đang xem ván chơi. Diễn biến công khai sẽ được gửi qua tin nhắn riêng.".to_string()
+                    }
+                    Ok(SpectateResult::Left) => "✅ Bạn đã thôi xem ván chơi này.".to_string(),
+                    Ok(SpectateResult::IsPlayer) => {
+                        "⚠️ Bạn đang là người chơi trong phòng này, không thể làm khán giả.".to_string()
+                    }
+                    Ok(SpectateResult::GameEnded) => "⚠️ Ván chơi đã kết thúc.".to_string(),
+                    Err(_) => "❌ Lỗi kết nối tới phòng chơi.".to_string(),
+                }
+            };
+
+            cmd.create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+
+            Ok(())
+        })
+    }
+}
@@ -10,20 +10,27 @@ impl SlashCommand for StartCommand {
         "masoi-start"
     }
 
+    fn description(&self) -> &'static str {
+        "Bắt đầu ván chơi (chỉ chủ phòng)"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
-            let guild_id = match cmd.guild_id {
-                Some(id) => id,
-                None => return Ok(()),
-            };
+            if cmd.guild_id.is_none() {
+                return Ok(());
+            }
 
-            let has_room = data.room_registry.read().await.contains_key(&guild_id);
+            let has_room = data
+                .room_registry
+                .read()
+                .await
+                .contains_key(&cmd.channel_id);
             if !has_room {
                 cmd.create_response(
                     &ctx.http,
                     CreateInteractionResponse::Message(
                         CreateInteractionResponseMessage::new()
-                            .content("Chưa có phòng chơi.")
+                            .content("Kênh này chưa có phòng chơi.")
                             .ephemeral(true),
                     ),
                 )
@@ -31,24 +38,12 @@ impl SlashCommand for StartCommand {
                 return Ok(());
             }
 
-            let row = CreateActionRow::Buttons(vec![
-                CreateButton::new("start_default")
-                    .label("Dùng vai trò mặc định")
-                    .style(ButtonStyle::Primary),
-                CreateButton::new("start_custom_json")
-                    .label("Tuỳ chỉnh (JSON)")
-                    .style(ButtonStyle::Secondary),
-                CreateButton::new("start_custom_name")
-                    .label("Tuỳ chỉnh (Tên)")
-                    .style(ButtonStyle::Secondary),
-            ]);
-
             cmd.create_response(
                 &ctx.http,
                 CreateInteractionResponse::Message(
                     CreateInteractionResponseMessage::new()
                         .content("🎮 Chọn cách phân vai trò:")
-                        .components(vec![row])
+                        .components(vec![start_options_row()])
                         .ephemeral(true),
                 ),
             )
@@ -58,3 +53,18 @@ impl SlashCommand for StartCommand {
         })
     }
 }
+
+/// Các nút chọn cách phân vai trò trước khi bắt đầu ván.
+pub fn start_options_row() -> CreateActionRow {
+    CreateActionRow::Buttons(vec![
+        CreateButton::new("start_default")
+            .label("Dùng vai trò mặc định")
+            .style(ButtonStyle::Primary),
+        CreateButton::new("start_custom_json")
+            .label("Tuỳ chỉnh (JSON)")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new("start_custom_name")
+            .label("Tuỳ chỉnh (Tên)")
+            .style(ButtonStyle::Secondary),
+    ])
+}
@@ -0,0 +1,98 @@
+use crate::bot::BotData;
+use crate::commands::{CommandFuture, SlashCommand};
+use crate::utils::role::RoleId;
+use serenity::all::*;
+use std::sync::Arc;
+
+pub struct StatsCommand;
+
+impl SlashCommand for StatsCommand {
+    fn name(&self) -> &'static str {
+        "masoi-stats"
+    }
+
+    fn description(&self) -> &'static str {
+        "Xem thống kê thắng / thua của người chơi"
+    }
+
+    fn options(&self) -> Vec<CreateCommandOption> {
+        vec![CreateCommandOption::new(
+            CommandOptionType::User,
+            "user",
+            "Người chơi muốn xem (mặc định là bạn)",
+        )
+        .required(false)]
+    }
+
+    fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
+        Box::pin(async move {
+            let user = cmd
+                .data
+                .options
+                .iter()
+                .find_map(|opt| match opt.value {
+                    CommandDataOptionValue::User(id) => cmd.data.resolved.users.get(&id),
+                    _ => None,
+                })
+                .unwrap_or(&cmd.user)
+                .clone();
+
+            cmd.defer(&ctx.http).await?;
+
+            let stats = data.stats.player_stats(user.id).await?;
+
+            if stats.overall.games == 0 {
+                cmd.edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(format!("📭 **{}** chưa chơi ván Ma Sói nào.", user.name)),
+                )
+                .await?;
+                return Ok(());
+            }
+
+            let mut roles: Vec<_> = stats.roles.iter().collect();
+            roles.sort_by_key(|(_, s)| std::cmp::Reverse(s.games));
+
+            let role_lines = roles
+                .iter()
+                .map(|(role_id, s)| {
+                    let name = RoleId::from_u8(**role_id).map(|r| r.name()).unwrap_or("?");
+                    format!(
+                        "**{}** — {}/{} thắng ({:.0}%)",
+                        name,
+                        s.wins,
+                        s.games,
+                        s.win_rate()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let overall = &stats.overall;
+            let embed = CreateEmbed::new()
+                .color(0x9b59b6)
+                .title(format!("📊 THỐNG KÊ CỦA {}", user.name.to_uppercase()))
+                .thumbnail(user.face())
+                .field("🎮 Số ván", format!("> {}", overall.games), true)
+                .field("🏆 Thắng", format!("> {}", overall.wins), true)
+                .field(
+                    "📈 Tỉ lệ thắng",
+                    format!("> {:.1}%", overall.win_rate()),
+                    true,
+                )
+                .field(
+                    "❤️ Sống sót",
+                    format!("> {}/{}", overall.survived, overall.games),
+                    true,
+                )
+                .field("🎭 Theo vai trò", role_lines, false)
+                .timestamp(Timestamp::now());
+
+            cmd.edit_response(&ctx.http, EditInteractionResponse::new().add_embed(embed))
+                .await?;
+
+            Ok(())
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use crate::bot::BotData;
 use crate::commands::{CommandFuture, SlashCommand};
+use crate::game::room::guild_room_channels;
 use crate::game::{RoomEvent, RoomStatus};
 use serenity::all::*;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 
@@ -12,6 +14,10 @@ impl SlashCommand for StatusCommand {
         "status"
     }
 
+    fn description(&self) -> &'static str {
+        "Xem trạng thái phòng chơi hiện tại"
+    }
+
     fn run(&self, ctx: Context, cmd: CommandInteraction, data: Arc<BotData>) -> CommandFuture {
         Box::pin(async move {
             let guild_id = match cmd.guild_id {
@@ -30,18 +36,34 @@ impl SlashCommand for StatusCommand {
                 }
             };
 
-            let room_handle = {
+            let (room_handle, other_channels) = {
                 let registry = data.room_registry.read().await;
-                registry.get(&guild_id).cloned()
+                (
+                    registry.get(&cmd.channel_id).cloned(),
+                    guild_room_channels(&registry, guild_id),
+                )
             };
 
             let room_handle = match room_handle {
                 Some(h) => h,
                 None => {
-                    let embed = CreateEmbed::new()
+                    let mut embed = CreateEmbed::new()
                         .color(0x95a5a6)
                         .title("🎮 TRẠNG THÁI PHÒNG MA SÓI")
-                        .description("```⚠️ Hiện không có phòng Ma Sói nào trong server!```")
+                        .description("```⚠️ Hiện không có phòng Ma Sói nào trong kênh này!```");
+                    if !other_channels.is_empty() {
+                        let channels = other_channels
+                            .iter()
+                            .map(|c| format!("<#{}>", c))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        embed = embed.field(
+                            "🏠 Phòng Khác Trong Server",
+                            format!("> {}", channels),
+                            false,
+                        );
+                    }
+                    let embed = embed
                         .field("💡 Hướng Dẫn", "> Sử dụng lệnh `/masoi-create` để tạo phòng mới\n> Sử dụng `/huongdan` để xem hướng dẫn chi tiết", false)
                         .footer(CreateEmbedFooter::new("Hẹ hẹ hẹ"))
                         .timestamp(Timestamp::now());
@@ -115,6 +137,9 @@ impl SlashCommand for StatusCommand {
                     crate::game::state::Phase::Night => ("🌙", "Ban Đêm"),
                     crate::game::state::Phase::Day => ("☀️", "Ban Ngày"),
                     crate::game::state::Phase::Voting => ("🗳️", "Bỏ Phiếu"),
+                    crate::game::state::Phase::Election => ("🎖️", "Bầu Thị Trưởng"),
+                    crate::game::state::Phase::Defense => ("🎤", "Bào Chữa"),
+                    crate::game::state::Phase::Judgment => ("⚖️", "Phán Quyết"),
                     _ => ("❓", "Khác"),
                 };
 
@@ -128,6 +153,15 @@ impl SlashCommand for StatusCommand {
                     .field("💀 Đã Chết", format!("> {}", dead_count), true);
             }
 
+            let player_ids: Vec<UserId> = snapshot.players.iter().map(|p| p.user_id).collect();
+            let ratings = match data.ratings.get_many(guild_id, &player_ids).await {
+                Ok(ratings) => ratings,
+                Err(e) => {
+                    tracing::error!("Lỗi tải rating server {}: {:?}", guild_id, e);
+                    HashMap::new()
+                }
+            };
+
             let player_list_str = if snapshot.players.is_empty() {
                 "> *Chưa có người chơi nào tham gia*".to_string()
             } else {
@@ -140,8 +174,15 @@ impl SlashCommand for StatusCommand {
                         let status_icon = if p.alive { "🟢" } else { "💀" };
                         let number = format!("{:02}", index + 1);
                         let crown = if is_host { " 👑" } else { "" };
-
-                        format!("`{}` {} **{}**{}", number, status_icon, p.name, crown)
+                        let rating = ratings
+                            .get(&p.user_id)
+                            .map(|r| format!(" · `{:.0}`", r))
+                            .unwrap_or_default();
+
+                        format!(
+                            "`{}` {} **{}**{}{}",
+                            number, status_icon, p.name, rating, crown
+                        )
                     })
                     .collect::<Vec<_>>()
                     .join("\n")
@@ -1 +1,22 @@
 pub const MAX_FILE_SIZE: u32 = 8 * 1024 * 1024;
+
+pub const MIN_PLAYERS: usize = 4;
+pub const MAX_PLAYERS: usize = 18;
+
+/// Số phòng chơi tối đa chạy cùng lúc trong một server.
+pub const MAX_ROOMS_PER_GUILD: usize = 3;
+
+/// Phòng chờ không có ai vào / rời / sẵn sàng trong khoảng này (giây) sẽ bị đóng.
+pub const LOBBY_IDLE_TIME: u64 = 15 * 60;
+
+/// Ngưỡng AFK mặc định và tối đa có thể chọn trong /masoi-settings.
+pub const DEFAULT_AFK_THRESHOLD: u32 = 2;
+pub const MAX_AFK_THRESHOLD: u32 = 5;
+
+/// Thời gian (giây) bào chữa và phán quyết trong chế độ ngày cổ điển.
+pub const DEFENSE_TIME: u64 = 30;
+pub const JUDGMENT_TIME: u64 = 30;
+
+/// Giới hạn (giây) cho mỗi mốc thời gian trong /masoi-settings.
+pub const MIN_PHASE_TIME: u64 = 10;
+pub const MAX_PHASE_TIME: u64 = 300;
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use mongodb::{
+    bson::{doc, oid::ObjectId},
+    options::FindOptions,
+    Collection, Database,
+};
+use serenity::all::GuildId;
+
+use crate::db::models::GameRecord;
+
+/// Lưu biên bản các ván đã kết thúc trong collection `game_records`.
+#[derive(Clone)]
+pub struct HistoryRepository {
+    collection: Collection<GameRecord>,
+}
+
+impl HistoryRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("game_records"),
+        }
+    }
+
+    pub async fn save(&self, record: &GameRecord) -> Result<()> {
+        self.collection.insert_one(record, None).await?;
+        Ok(())
+    }
+
+    /// Các ván gần nhất của server, mới nhất trước (không kèm nhật ký sự kiện).
+    pub async fn recent(&self, guild_id: GuildId, limit: i64) -> Result<Vec<GameRecord>> {
+        let options = FindOptions::builder()
+            .sort(doc! { "finishedAt": -1 })
+            .limit(limit)
+            .projection(doc! { "events": 0 })
+            .build();
+
+        let mut cursor = self
+            .collection
+            .find(doc! { "guildId": guild_id.to_string() }, options)
+            .await?;
+
+        let mut records = Vec::new();
+        while cursor.advance().await? {
+            match cursor.deserialize_current() {
+                Ok(record) => records.push(record),
+                Err(e) => tracing::error!("Bỏ qua biên bản ván chơi bị lỗi: {:?}", e),
+            }
+        }
+
+        Ok(records)
+    }
+
+    pub async fn get(&self, guild_id: GuildId, id: ObjectId) -> Result<Option<GameRecord>> {
+        let record = self
+            .collection
+            .find_one(doc! { "_id": id, "guildId": guild_id.to_string() }, None)
+            .await?;
+
+        Ok(record)
+    }
+}
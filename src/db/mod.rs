@@ -1,4 +1,9 @@
+pub mod history;
 pub mod models;
+pub mod ratings;
+pub mod rooms;
+pub mod settings;
+pub mod stats;
 
 use anyhow::Result;
 use mongodb::{Client, Database};
@@ -12,4 +17,9 @@ pub async fn connect_mongodb(uri: &str) -> Result<Database> {
     Ok(db)
 }
 
+pub use history::HistoryRepository;
 pub use models::*;
+pub use ratings::RatingRepository;
+pub use rooms::RoomRepository;
+pub use settings::SettingsRepository;
+pub use stats::StatsRepository;
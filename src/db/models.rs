@@ -1,5 +1,11 @@
 use mongodb::bson::oid::ObjectId;
 use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+
+use crate::constants::DEFAULT_AFK_THRESHOLD;
+use crate::game::events::GameEvent;
+use crate::types::{DeathCause, Faction};
+use crate::utils::role::RoleId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerSettings {
@@ -20,6 +26,37 @@ pub struct ServerSettings {
 
     #[serde(rename = "voteTime")]
     pub vote_time: u64,
+
+    #[serde(rename = "balancedShuffle", default)]
+    pub balanced_shuffle: bool,
+
+    /// Người chơi phải bấm "Sẵn sàng", phòng tự bắt đầu khi tất cả đã sẵn sàng.
+    #[serde(rename = "readyCheck", default)]
+    pub ready_check: bool,
+
+    /// Số giây đếm ngược tự bắt đầu khi phòng đủ người, 0 là tắt.
+    #[serde(rename = "autoStartTime", default)]
+    pub auto_start_time: u64,
+
+    /// Số lượt bỏ lỡ hành động liên tiếp trước khi bị cảnh báo, 0 là tắt.
+    #[serde(rename = "afkThreshold", default = "default_afk_threshold")]
+    pub afk_threshold: u32,
+
+    /// Bầu Thị Trưởng vào đầu ngày đầu tiên.
+    #[serde(rename = "mayorElection", default)]
+    pub mayor_election: bool,
+
+    /// Ngày cổ điển: tố cáo, bào chữa rồi phán quyết thay cho bỏ phiếu treo cổ.
+    #[serde(rename = "classicDay", default)]
+    pub classic_day: bool,
+
+    /// Các vai trò không được chọn khi chia vai tự động.
+    #[serde(rename = "bannedRoles", default)]
+    pub banned_roles: Vec<RoleId>,
+}
+
+fn default_afk_threshold() -> u32 {
+    DEFAULT_AFK_THRESHOLD
 }
 
 impl Default for ServerSettings {
@@ -31,6 +68,111 @@ impl Default for ServerSettings {
             night_time: 70,
             discuss_time: 90,
             vote_time: 30,
+            balanced_shuffle: false,
+            ready_check: false,
+            auto_start_time: 0,
+            afk_threshold: DEFAULT_AFK_THRESHOLD,
+            mayor_election: false,
+            classic_day: false,
+            banned_roles: Vec::new(),
         }
     }
 }
+
+/// Kết quả của một người chơi trong một ván đã kết thúc.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerGameRecord {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    #[serde(rename = "guildId")]
+    pub guild_id: String,
+
+    #[serde(rename = "userId")]
+    pub user_id: String,
+
+    pub name: String,
+
+    #[serde(rename = "roleId")]
+    pub role_id: RoleId,
+
+    pub faction: Faction,
+
+    pub won: bool,
+
+    pub survived: bool,
+
+    #[serde(rename = "deathCause")]
+    pub death_cause: Option<DeathCause>,
+
+    #[serde(rename = "nightCount")]
+    pub night_count: i32,
+
+    #[serde(rename = "finishedAt")]
+    pub finished_at: i64,
+}
+
+/// Điểm rating của một người chơi trong một server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRating {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    #[serde(rename = "guildId")]
+    pub guild_id: String,
+
+    #[serde(rename = "userId")]
+    pub user_id: String,
+
+    pub rating: f64,
+
+    #[serde(default)]
+    pub games: u32,
+}
+
+/// Biên bản đầy đủ của một ván đã kết thúc: vai trò và mọi sự kiện theo từng đêm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    #[serde(rename = "guildId")]
+    pub guild_id: String,
+
+    pub winner: Faction,
+
+    #[serde(rename = "soloWinners", default)]
+    pub solo_winners: Vec<UserId>,
+
+    #[serde(rename = "nightCount")]
+    pub night_count: i32,
+
+    pub players: Vec<GameRecordPlayer>,
+
+    /// Bỏ trống khi chỉ tải danh sách ván (không lấy nhật ký).
+    #[serde(default)]
+    pub events: Vec<GameEvent>,
+
+    #[serde(rename = "finishedAt")]
+    pub finished_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecordPlayer {
+    #[serde(rename = "userId")]
+    pub user_id: UserId,
+
+    pub name: String,
+
+    #[serde(rename = "roleId")]
+    pub role_id: RoleId,
+
+    pub faction: Faction,
+
+    pub won: bool,
+
+    pub survived: bool,
+
+    #[serde(rename = "deathCause")]
+    pub death_cause: Option<DeathCause>,
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use mongodb::{bson::doc, options::UpdateOptions, Collection, Database};
+use serenity::all::{GuildId, UserId};
+
+use crate::db::models::PlayerRating;
+use crate::game::rating::DEFAULT_RATING;
+
+/// Lưu điểm rating của người chơi theo từng server trong collection `player_ratings`.
+#[derive(Clone)]
+pub struct RatingRepository {
+    collection: Collection<PlayerRating>,
+}
+
+impl RatingRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("player_ratings"),
+        }
+    }
+
+    /// Rating hiện tại của các người chơi, ai chưa có sẽ nhận `DEFAULT_RATING`.
+    pub async fn get_many(
+        &self,
+        guild_id: GuildId,
+        user_ids: &[UserId],
+    ) -> Result<HashMap<UserId, f64>> {
+        let ids: Vec<String> = user_ids.iter().map(|id| id.to_string()).collect();
+        let mut cursor = self
+            .collection
+            .find(
+                doc! { "guildId": guild_id.to_string(), "userId": { "$in": ids } },
+                None,
+            )
+            .await?;
+
+        let mut ratings: HashMap<UserId, f64> =
+            user_ids.iter().map(|id| (*id, DEFAULT_RATING)).collect();
+
+        while cursor.advance().await? {
+            let record = cursor.deserialize_current()?;
+            if let Ok(id) = record.user_id.parse::<u64>() {
+                ratings.insert(UserId::new(id), record.rating);
+            }
+        }
+
+        Ok(ratings)
+    }
+
+    /// Ghi rating mới sau một ván và tăng số ván đã tính điểm.
+    pub async fn save_many(&self, guild_id: GuildId, ratings: &HashMap<UserId, f64>) -> Result<()> {
+        let options = UpdateOptions::builder().upsert(true).build();
+
+        for (user_id, rating) in ratings.iter() {
+            self.collection
+                .update_one(
+                    doc! { "guildId": guild_id.to_string(), "userId": user_id.to_string() },
+                    doc! { "$set": { "rating": *rating }, "$inc": { "games": 1 } },
+                    options.clone(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
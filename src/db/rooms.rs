@@ -0,0 +1,54 @@
+use anyhow::Result;
+use mongodb::{bson::doc, options::ReplaceOptions, Collection, Database};
+use serenity::all::ChannelId;
+
+use crate::game::persistence::SavedRoom;
+
+/// Lưu snapshot các phòng đang chơi trong collection `room_snapshots`.
+#[derive(Clone)]
+pub struct RoomRepository {
+    collection: Collection<SavedRoom>,
+}
+
+impl RoomRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("room_snapshots"),
+        }
+    }
+
+    pub async fn save(&self, room: &SavedRoom) -> Result<()> {
+        let options = ReplaceOptions::builder().upsert(true).build();
+        self.collection
+            .replace_one(
+                doc! { "channel_id": room.channel_id.to_string() },
+                room,
+                options,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, channel_id: ChannelId) -> Result<()> {
+        self.collection
+            .delete_one(doc! { "channel_id": channel_id.to_string() }, None)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn load_all(&self) -> Result<Vec<SavedRoom>> {
+        let mut cursor = self.collection.find(None, None).await?;
+        let mut rooms = Vec::new();
+
+        while cursor.advance().await? {
+            match cursor.deserialize_current() {
+                Ok(room) => rooms.push(room),
+                Err(e) => tracing::error!("Bỏ qua snapshot phòng bị lỗi: {:?}", e),
+            }
+        }
+
+        Ok(rooms)
+    }
+}
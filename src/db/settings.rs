@@ -0,0 +1,41 @@
+use anyhow::Result;
+use mongodb::{bson::doc, options::ReplaceOptions, Collection, Database};
+use serenity::all::GuildId;
+
+use crate::db::models::ServerSettings;
+
+/// Đọc / ghi cài đặt thời gian của từng server trong collection `server_settings`.
+#[derive(Clone)]
+pub struct SettingsRepository {
+    collection: Collection<ServerSettings>,
+}
+
+impl SettingsRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("server_settings"),
+        }
+    }
+
+    /// Lấy cài đặt của server, trả về mặc định nếu server chưa lưu gì.
+    pub async fn get(&self, guild_id: GuildId) -> Result<ServerSettings> {
+        let found = self
+            .collection
+            .find_one(doc! { "guildId": guild_id.to_string() }, None)
+            .await?;
+
+        Ok(found.unwrap_or_else(|| ServerSettings {
+            guild_id: guild_id.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    pub async fn save(&self, settings: &ServerSettings) -> Result<()> {
+        let options = ReplaceOptions::builder().upsert(true).build();
+        self.collection
+            .replace_one(doc! { "guildId": &settings.guild_id }, settings, options)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use mongodb::{bson::doc, Collection, Database};
+use serenity::all::{GuildId, UserId};
+
+use crate::db::models::PlayerGameRecord;
+
+/// Thống kê thắng / thua của một nhóm ván chơi.
+#[derive(Debug, Clone, Default)]
+pub struct WinStats {
+    pub games: u32,
+    pub wins: u32,
+    pub survived: u32,
+}
+
+impl WinStats {
+    pub fn add(&mut self, record: &PlayerGameRecord) {
+        self.games += 1;
+        if record.won {
+            self.wins += 1;
+        }
+        if record.survived {
+            self.survived += 1;
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 * 100.0 / self.games as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PlayerStats {
+    pub overall: WinStats,
+    pub roles: BTreeMap<u8, WinStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardEntry {
+    pub user_id: UserId,
+    pub name: String,
+    pub stats: WinStats,
+}
+
+/// Lưu kết quả từng người chơi sau mỗi ván trong collection `player_games`.
+#[derive(Clone)]
+pub struct StatsRepository {
+    collection: Collection<PlayerGameRecord>,
+}
+
+impl StatsRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection("player_games"),
+        }
+    }
+
+    pub async fn record_game(&self, records: &[PlayerGameRecord]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        self.collection.insert_many(records, None).await?;
+        Ok(())
+    }
+
+    /// Thống kê của người chơi trên mọi server.
+    pub async fn player_stats(&self, user_id: UserId) -> Result<PlayerStats> {
+        let mut cursor = self
+            .collection
+            .find(doc! { "userId": user_id.to_string() }, None)
+            .await?;
+
+        let mut stats = PlayerStats::default();
+        while cursor.advance().await? {
+            let record = cursor.deserialize_current()?;
+            stats.overall.add(&record);
+            stats
+                .roles
+                .entry(record.role_id as u8)
+                .or_default()
+                .add(&record);
+        }
+
+        Ok(stats)
+    }
+
+    /// Bảng xếp hạng người chơi của một server, sắp theo số trận thắng rồi tỉ lệ thắng.
+    pub async fn leaderboard(
+        &self,
+        guild_id: GuildId,
+        limit: usize,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let mut cursor = self
+            .collection
+            .find(doc! { "guildId": guild_id.to_string() }, None)
+            .await?;
+
+        let mut players: HashMap<String, LeaderboardEntry> = HashMap::new();
+        while cursor.advance().await? {
+            let record = cursor.deserialize_current()?;
+            let Ok(user_id) = record.user_id.parse::<u64>() else {
+                continue;
+            };
+
+            let entry = players
+                .entry(record.user_id.clone())
+                .or_insert_with(|| LeaderboardEntry {
+                    user_id: UserId::new(user_id),
+                    name: record.name.clone(),
+                    stats: WinStats::default(),
+                });
+            entry.name = record.name.clone();
+            entry.stats.add(&record);
+        }
+
+        let mut entries: Vec<LeaderboardEntry> = players.into_values().collect();
+        entries.sort_by(|a, b| {
+            b.stats
+                .wins
+                .cmp(&a.stats.wins)
+                .then(b.stats.win_rate().total_cmp(&a.stats.win_rate()))
+        });
+        entries.truncate(limit);
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::game::helper::player_is_dead;
+use crate::game::room::GameRoom;
+use crate::types::{DeathCause, DeathRecord, Player};
+use crate::utils::role::RoleId;
+
+/// Vai trò buộc phải hành động mỗi đêm: Sói bỏ phiếu cắn, Tiên Tri / Bảo Vệ / Thám Tử dùng chức năng.
+pub fn requires_night_action(player: &Player) -> bool {
+    if !player.alive {
+        return false;
+    }
+
+    match player.role.id() {
+        role_id if role_id.bites() => true,
+        RoleId::Seer | RoleId::Bodyguard | RoleId::Detective => player.can_use_skill,
+        _ => false,
+    }
+}
+
+pub fn requires_vote(player: &Player) -> bool {
+    player.alive && player.can_vote
+}
+
+/// Cộng một lượt bỏ lỡ cho những ai phải hành động trong phase vừa kết thúc mà không làm gì.
+/// Chạm ngưỡng thì bị cảnh báo, bỏ lỡ thêm lần nữa thì bị coi là đã bỏ làng ra đi.
+pub async fn process_afk(room: &mut GameRoom, required: impl Fn(&Player) -> bool) {
+    let active = std::mem::take(&mut room.active_players);
+    if std::mem::take(&mut room.afk_exempt) {
+        return;
+    }
+    for user_id in active.iter() {
+        room.afk_strikes.remove(user_id);
+    }
+
+    let threshold = room.settings.afk_threshold;
+    if threshold == 0 {
+        return;
+    }
+
+    let mut warned = Vec::new();
+    let mut removed = Vec::new();
+    for player in room.players.iter().filter(|p| required(p)) {
+        if active.contains(&player.user_id) {
+            continue;
+        }
+
+        let strikes = room.afk_strikes.entry(player.user_id).or_insert(0);
+        *strikes += 1;
+        if *strikes > threshold {
+            removed.push(player.user_id);
+        } else if *strikes == threshold {
+            warned.push((player.user_id, *strikes));
+        }
+    }
+
+    for (user_id, strikes) in warned {
+        let _ = room
+            .notifier
+            .say(
+                user_id,
+                format!(
+                    "⚠️ Bạn đã bỏ lỡ **{}** lượt hành động liên tiếp. Nếu lần tới vẫn không hành động, bạn sẽ bị coi là đã bỏ làng ra đi.",
+                    strikes
+                ),
+            )
+            .await;
+    }
+
+    let night = room.game_state.night_count;
+    for user_id in removed {
+        room.afk_strikes.remove(&user_id);
+
+        let Some(player) = room.players.iter_mut().find(|p| p.user_id == user_id) else {
+            continue;
+        };
+        let name = player.name.clone();
+        let role_name = player.role.id().name();
+        player_is_dead(player, night, DeathCause::LeftVillage);
+
+        room.game_state.record_deaths(&[DeathRecord {
+            user_id,
+            night,
+            cause: DeathCause::LeftVillage,
+        }]);
+
+        let _ = room
+            .notifier
+            .say(
+                user_id,
+                "💤 Bạn đã bị loại khỏi ván vì không hoạt động quá lâu.",
+            )
+            .await;
+        room.announce(&format!(
+            "🚪 **{}** đã bỏ làng ra đi vì không hoạt động. Vai trò của họ là **{}**.",
+            name, role_name
+        ))
+        .await;
+    }
+}
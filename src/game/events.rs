@@ -0,0 +1,93 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+
+use crate::game::state::Phase;
+use crate::types::{DeathCause, Faction};
+use crate::utils::role::RoleId;
+
+/// Một sự kiện trong ván chơi, kèm đêm xảy ra và thời điểm (unix mili giây).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameEvent {
+    pub night: i32,
+    pub timestamp: i64,
+    pub kind: GameEventKind,
+}
+
+impl GameEvent {
+    pub fn new(night: i32, kind: GameEventKind) -> Self {
+        Self {
+            night,
+            timestamp: Utc::now().timestamp_millis(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum GameEventKind {
+    RoleAssigned {
+        user_id: UserId,
+        role_id: RoleId,
+    },
+    PhaseChanged {
+        phase: Phase,
+    },
+    WolfVote {
+        voter: UserId,
+        target: UserId,
+    },
+    /// `result` là phe mà Tiên Tri nhìn thấy (có thể bị Sói Trùm che giấu).
+    SeerView {
+        seer: UserId,
+        target: UserId,
+        result: Faction,
+    },
+    DetectiveCheck {
+        detective: UserId,
+        targets: (UserId, UserId),
+        same_faction: bool,
+    },
+    Protect {
+        bodyguard: UserId,
+        target: UserId,
+    },
+    Poison {
+        witch: UserId,
+        target: UserId,
+    },
+    Heal {
+        witch: UserId,
+        target: UserId,
+    },
+    /// `target` là `None` khi người chơi bỏ qua lượt bỏ phiếu.
+    HangVote {
+        voter: UserId,
+        target: Option<UserId>,
+    },
+    Accusation {
+        accuser: UserId,
+        target: UserId,
+    },
+    /// `guilty` là `true` khi người chơi chọn treo cổ người bị xét xử.
+    JudgmentVote {
+        voter: UserId,
+        guilty: bool,
+    },
+    Death {
+        user_id: UserId,
+        cause: DeathCause,
+    },
+    SoloWin {
+        user_id: UserId,
+        role_id: RoleId,
+    },
+    /// Được bầu hoặc được Thị Trưởng cũ chọn làm người kế nhiệm.
+    MayorElected {
+        user_id: UserId,
+    },
+    GameEnded {
+        winner: Faction,
+    },
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, FixedOffset};
+use serenity::all::UserId;
+
+use crate::db::{GameRecord, GameRecordPlayer};
+use crate::game::recap::summarize_nights;
+use crate::utils::role::RoleId;
+
+/// Giờ Việt Nam (GMT+7) dùng khi hiển thị thời điểm kết thúc ván.
+const VN_OFFSET_SECS: i32 = 7 * 3600;
+
+/// Thời điểm (unix mili giây) theo dạng `18/10/2026 21:30`.
+pub fn format_time(timestamp_ms: i64) -> String {
+    let offset = FixedOffset::east_opt(VN_OFFSET_SECS).expect("offset hợp lệ");
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .map(|time| {
+            time.with_timezone(&offset)
+                .format("%d/%m/%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "?".to_string())
+}
+
+/// Đội hình của ván, ví dụ "2× Ma Sói, 1× Tiên Tri".
+pub fn composition_summary(players: &[GameRecordPlayer]) -> String {
+    let mut counts: Vec<(RoleId, usize)> = Vec::new();
+    for player in players.iter() {
+        match counts.iter_mut().find(|(role, _)| *role == player.role_id) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((player.role_id, 1)),
+        }
+    }
+    counts.sort_by_key(|(role, _)| *role as u8);
+
+    counts
+        .into_iter()
+        .map(|(role, count)| format!("{}× {}", count, role.name()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn record_json(record: &GameRecord) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(record)
+}
+
+/// Biên bản ván chơi dạng Markdown: tổng quan, bảng vai trò và diễn biến từng đêm.
+pub fn record_markdown(record: &GameRecord) -> String {
+    let name = |user_id: UserId| {
+        record
+            .players
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .map(|p| format!("**{}**", escape(&p.name)))
+            .unwrap_or_else(|| format!("<@{}>", user_id))
+    };
+
+    let mut lines = vec![
+        format!("# Ván Ma Sói — {} (GMT+7)", format_time(record.finished_at)),
+        String::new(),
+        format!("- 🏆 Phe chiến thắng: **{}**", record.winner.name()),
+        format!("- 🌙 Số đêm đã qua: **{}**", record.night_count),
+        format!("- 👥 Số người chơi: **{}**", record.players.len()),
+        format!("- 🎭 Đội hình: {}", composition_summary(&record.players)),
+    ];
+
+    if !record.solo_winners.is_empty() {
+        let names = record
+            .solo_winners
+            .iter()
+            .map(|id| name(*id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(format!("- 🎪 Thắng riêng: {}", names));
+    }
+
+    lines.push(String::new());
+    lines.push("## Người chơi".to_string());
+    lines.push(String::new());
+    lines.push("| # | Người chơi | Vai trò | Phe | Kết quả | Trạng thái |".to_string());
+    lines.push("|---|---|---|---|---|---|".to_string());
+    for (index, player) in record.players.iter().enumerate() {
+        let status = match (player.survived, player.death_cause) {
+            (true, _) => "🟢 Sống sót".to_string(),
+            (false, Some(cause)) => format!("💀 {}", cause.name()),
+            (false, None) => "💀 Đã chết".to_string(),
+        };
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {} |",
+            index + 1,
+            escape(&player.name),
+            player.role_id.name(),
+            player.faction.name(),
+            if player.won { "Thắng" } else { "Thua" },
+            status
+        ));
+    }
+
+    for summary in summarize_nights(&record.events, &name) {
+        lines.push(String::new());
+        lines.push(format!("## Đêm {}", summary.night));
+        lines.push(String::new());
+        lines.push("### 🌙 Ban đêm".to_string());
+        lines.extend(summary.night_lines.iter().map(|line| bullet(line)));
+
+        if !summary.day_lines.is_empty() {
+            lines.push(String::new());
+            lines.push("### ☀️ Ban ngày".to_string());
+            lines.extend(summary.day_lines.iter().map(|line| bullet(line)));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Dòng bắt đầu bằng "> " (chi tiết phiếu bầu) được thụt vào thành mục con.
+fn bullet(line: &str) -> String {
+    match line.strip_prefix("> ") {
+        Some(detail) => format!("  - {}", detail),
+        None => format!("- {}", line),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+}
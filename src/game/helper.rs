@@ -1,6 +1,7 @@
 use crate::roles::*;
-use crate::types::{Faction, Player};
+use crate::types::{DeathCause, DeathRecord, Faction, Player, Role};
 use crate::utils::role::RoleId;
+use serde::{Deserialize, Serialize};
 use serenity::model::id::UserId;
 use std::collections::HashMap;
 
@@ -8,6 +9,15 @@ use std::collections::HashMap;
 pub struct VictoryResult {
     pub winner: Winner,
     pub faction: Faction,
+    pub solo_winners: Vec<UserId>,
+}
+
+/// Một người chơi phe solo đã đạt điều kiện thắng riêng.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoloWin {
+    pub user_id: UserId,
+    pub role_id: RoleId,
+    pub ends_game: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -17,7 +27,30 @@ pub enum Winner {
     Solo,
 }
 
-pub fn check_victory(players: &[Player]) -> Option<VictoryResult> {
+/// Người chơi có thuộc phe / nhóm thắng cuộc hay không.
+pub fn player_won(player: &Player, victory: &VictoryResult) -> bool {
+    if victory.solo_winners.contains(&player.user_id) {
+        return true;
+    }
+
+    matches!(
+        (&victory.winner, player.role.faction()),
+        (Winner::Werewolf, Faction::Werewolf)
+            | (Winner::Village, Faction::Village | Faction::ViWolf)
+    )
+}
+
+pub fn check_victory(players: &[Player], solo_wins: &[SoloWin]) -> Option<VictoryResult> {
+    let solo_winners: Vec<UserId> = solo_wins.iter().map(|w| w.user_id).collect();
+
+    if solo_wins.iter().any(|w| w.ends_game) {
+        return Some(VictoryResult {
+            winner: Winner::Solo,
+            faction: Faction::Solo,
+            solo_winners,
+        });
+    }
+
     let alive_players: Vec<&Player> = players.iter().filter(|p| p.alive).collect();
     let alive_wolves: Vec<&Player> = alive_players
         .iter()
@@ -31,9 +64,16 @@ pub fn check_victory(players: &[Player]) -> Option<VictoryResult> {
         .collect();
 
     if alive_players.len() == alive_solos.len() && !alive_solos.is_empty() {
+        let mut solo_winners = solo_winners;
+        for p in alive_solos {
+            if !solo_winners.contains(&p.user_id) {
+                solo_winners.push(p.user_id);
+            }
+        }
         return Some(VictoryResult {
             winner: Winner::Solo,
             faction: Faction::Solo,
+            solo_winners,
         });
     }
 
@@ -41,6 +81,7 @@ pub fn check_victory(players: &[Player]) -> Option<VictoryResult> {
         return Some(VictoryResult {
             winner: Winner::Village,
             faction: Faction::Village,
+            solo_winners,
         });
     }
 
@@ -49,25 +90,21 @@ pub fn check_victory(players: &[Player]) -> Option<VictoryResult> {
         return Some(VictoryResult {
             winner: Winner::Werewolf,
             faction: Faction::Werewolf,
+            solo_winners,
         });
     }
 
     None
 }
 
-pub fn process_vote(players: &mut [Player]) -> Option<(UserId, usize)> {
-    let mut fluence_player_id: Option<UserId> = None;
-
-    for player in players.iter() {
-        if let Some(wolffluence) = player.role.clone_box().downcast_ref::<Wolffluence>() {
-            if player.alive && wolffluence.influence_player.is_some() {
-                fluence_player_id = wolffluence.influence_player;
-                break;
-            }
-        }
-    }
+/// Kiểm phiếu treo cổ. Phiếu của Thị Trưởng (`mayor`) tính gấp đôi và quyết định khi hoà phiếu,
+/// nhưng ngưỡng 2 phiếu để treo cổ không tính phần nhân đôi này.
+pub fn process_vote(players: &mut [Player], mayor: Option<UserId>) -> Option<(UserId, usize)> {
+    let fluence_player_id = influenced_player(players);
 
     let mut total_votes: HashMap<String, usize> = HashMap::new();
+    let mut base_votes: HashMap<String, usize> = HashMap::new();
+    let mut mayor_choice: Option<String> = None;
 
     for player in players.iter() {
         if !player.alive {
@@ -79,17 +116,14 @@ pub fn process_vote(players: &mut [Player]) -> Option<(UserId, usize)> {
                 continue;
             }
 
-            let is_wolffluence = matches!(player.role.id(), RoleId::Wolffluence);
-
-            let is_influenced = fluence_player_id.map_or(false, |id| id == player.user_id);
-
-            if is_wolffluence && fluence_player_id.is_some() {
-                *total_votes.entry(voted.clone()).or_insert(0) += 2;
-            } else if is_influenced {
-                *total_votes.entry(voted).or_insert(0) += 0;
-            } else {
-                *total_votes.entry(voted).or_insert(0) += 1;
+            let weight = vote_weight(player, fluence_player_id, mayor);
+            if mayor == Some(player.user_id) && weight > 0 {
+                mayor_choice = Some(voted.clone());
             }
+
+            *base_votes.entry(voted.clone()).or_insert(0) +=
+                vote_weight(player, fluence_player_id, None);
+            *total_votes.entry(voted).or_insert(0) += weight;
         }
     }
 
@@ -109,7 +143,13 @@ pub fn process_vote(players: &mut [Player]) -> Option<(UserId, usize)> {
         }
     }
 
-    if candidates.len() == 1 && max_votes >= 2 {
+    if candidates.len() > 1 {
+        if let Some(choice) = mayor_choice.filter(|c| candidates.contains(c)) {
+            candidates = vec![choice];
+        }
+    }
+
+    if candidates.len() == 1 && base_votes[&candidates[0]] >= 2 {
         if let Ok(user_id) = candidates[0].parse::<u64>() {
             return Some((UserId::new(user_id), max_votes));
         }
@@ -118,6 +158,64 @@ pub fn process_vote(players: &mut [Player]) -> Option<(UserId, usize)> {
     None
 }
 
+/// Kiểm phiếu phán quyết, trả về (số phiếu treo cổ, số phiếu tha) đã tính trọng số.
+pub fn process_judgment(
+    players: &[Player],
+    votes: &HashMap<UserId, bool>,
+    mayor: Option<UserId>,
+) -> (usize, usize) {
+    let fluence_player_id = influenced_player(players);
+
+    let (mut guilty, mut innocent) = (0, 0);
+    for player in players.iter().filter(|p| p.alive) {
+        let Some(vote) = votes.get(&player.user_id) else {
+            continue;
+        };
+
+        let weight = vote_weight(player, fluence_player_id, mayor);
+        if *vote {
+            guilty += weight;
+        } else {
+            innocent += weight;
+        }
+    }
+
+    (guilty, innocent)
+}
+
+/// Người đang bị Sói Thao Túng khống chế phiếu bầu.
+fn influenced_player(players: &[Player]) -> Option<UserId> {
+    players.iter().find_map(|player| {
+        let wolffluence = player.role.downcast_ref::<Wolffluence>()?;
+        if player.alive {
+            wolffluence.influence_player
+        } else {
+            None
+        }
+    })
+}
+
+/// Sói Thao Túng đang khống chế ai đó được 2 phiếu, người bị khống chế mất phiếu,
+/// và phiếu của Thị Trưởng được nhân đôi.
+fn vote_weight(player: &Player, fluence_player_id: Option<UserId>, mayor: Option<UserId>) -> usize {
+    let is_wolffluence = matches!(player.role.id(), RoleId::Wolffluence);
+    let is_influenced = fluence_player_id.is_some_and(|id| id == player.user_id);
+
+    let weight = if is_wolffluence && fluence_player_id.is_some() {
+        2
+    } else if is_influenced {
+        0
+    } else {
+        1
+    };
+
+    if mayor == Some(player.user_id) {
+        weight * 2
+    } else {
+        weight
+    }
+}
+
 pub fn total_voted_wolves_solve(players: &[Player]) -> Option<UserId> {
     let mut total_votes: HashMap<UserId, usize> = HashMap::new();
 
@@ -126,10 +224,12 @@ pub fn total_voted_wolves_solve(players: &[Player]) -> Option<UserId> {
             continue;
         }
 
-        if let Some(werewolf) = player.role.clone_box().downcast_ref::<Werewolf>() {
-            if let Some(target) = werewolf.vote_bite {
-                *total_votes.entry(target).or_insert(0) += 1;
-            }
+        if !player.alive {
+            continue;
+        }
+
+        if let Some(target) = wolf_bite_vote(player) {
+            *total_votes.entry(target).or_insert(0) += 1;
         }
     }
 
@@ -156,6 +256,22 @@ pub fn total_voted_wolves_solve(players: &[Player]) -> Option<UserId> {
     None
 }
 
+pub fn wolf_bite_vote(player: &Player) -> Option<UserId> {
+    let role = &player.role;
+
+    if let Some(werewolf) = role.downcast_ref::<Werewolf>() {
+        werewolf.vote_bite
+    } else if let Some(kitten) = role.downcast_ref::<KittenWolf>() {
+        kitten.vote_bite
+    } else if let Some(voodoo) = role.downcast_ref::<VoodooWerewolf>() {
+        voodoo.vote_bite
+    } else if let Some(wolffluence) = role.downcast_ref::<Wolffluence>() {
+        wolffluence.vote_bite
+    } else {
+        None
+    }
+}
+
 pub fn is_activity(players: &[Player], role_id: RoleId) -> bool {
     for player in players.iter() {
         if player.role.id() != role_id {
@@ -204,7 +320,41 @@ pub fn is_activity(players: &[Player], role_id: RoleId) -> bool {
     false
 }
 
-pub fn player_is_dead(player: &mut Player, night_count: i32) {
+/// Người chơi có dùng chức năng trong đêm hay không (dùng cho Stalker).
+pub fn player_has_acted(player: &Player) -> bool {
+    let role = &player.role;
+
+    match player.role.id() {
+        role_id if role_id.bites() => wolf_bite_vote(player).is_some(),
+        RoleId::Seer => role
+            .downcast_ref::<Seer>()
+            .is_some_and(|s| s.view_count < 1),
+        RoleId::Bodyguard => role
+            .downcast_ref::<Bodyguard>()
+            .is_some_and(|b| b.protected_person.is_some()),
+        RoleId::Detective => role
+            .downcast_ref::<Detective>()
+            .is_some_and(|d| !d.investigated_targets.is_empty()),
+        RoleId::Witch => role
+            .downcast_ref::<Witch>()
+            .is_some_and(|w| w.healed_person.is_some() || w.poisoned_person.is_some()),
+        RoleId::FoxSpirit => role
+            .downcast_ref::<FoxSpirit>()
+            .is_some_and(|f| f.view_count < 1),
+        RoleId::AlphaWerewolf => role
+            .downcast_ref::<AlphaWerewolf>()
+            .is_some_and(|a| a.mask_wolf.is_some()),
+        RoleId::Puppeteer => role
+            .downcast_ref::<Puppeteer>()
+            .is_some_and(|p| p.target_wolf.is_some()),
+        RoleId::Stalker => role
+            .downcast_ref::<Stalker>()
+            .is_some_and(|s| s.killed_person.is_some()),
+        _ => false,
+    }
+}
+
+pub fn player_is_dead(player: &mut Player, night_count: i32, cause: DeathCause) {
     let loudmouth_player =
         if let Some(loudmouth) = player.role.clone_box().downcast_ref::<Loudmouth>() {
             loudmouth.reveal_player
@@ -215,18 +365,64 @@ pub fn player_is_dead(player: &mut Player, night_count: i32) {
     let faction = player.role.faction();
     let original_role_id = player.role.id();
 
+    let mut dead = Dead::new(original_role_id, night_count, loudmouth_player);
+    dead.death_cause = Some(cause);
+
     player.alive = false;
-    player.role = Box::new(Dead::new(original_role_id, night_count, loudmouth_player));
+    player.role = Box::new(dead);
 }
 
-trait AsAny {
-    fn as_any(&self) -> &dyn std::any::Any;
-}
+/// Xử lý các cái chết trong cùng một lượt rồi kiểm tra điều kiện thắng riêng của từng vai trò.
+///
+/// Vai trò của người vừa chết được giữ lại để họ vẫn có thể thắng nhờ chính cái chết đó
+/// (ví dụ Thằng Ngố bị treo cổ).
+pub fn apply_deaths(
+    players: &mut [Player],
+    deaths: &[(UserId, DeathCause)],
+    night_count: i32,
+) -> (Vec<DeathRecord>, Vec<SoloWin>) {
+    let mut records: Vec<DeathRecord> = Vec::new();
+    let mut dying_roles: HashMap<UserId, Box<dyn Role>> = HashMap::new();
+
+    for (user_id, cause) in deaths.iter() {
+        if let Some(player) = players
+            .iter_mut()
+            .find(|p| p.user_id == *user_id && p.alive)
+        {
+            dying_roles.insert(*user_id, player.role.clone_box());
+            player_is_dead(player, night_count, *cause);
+            records.push(DeathRecord {
+                user_id: *user_id,
+                night: night_count,
+                cause: *cause,
+            });
+        }
+    }
 
-impl<T: 'static> AsAny for T {
-    fn as_any(&self) -> &dyn std::any::Any {
-        self
+    let mut wins: Vec<SoloWin> = Vec::new();
+    for record in records.iter() {
+        for player in players.iter() {
+            let role = match dying_roles.get(&player.user_id) {
+                Some(role) => role,
+                None if player.alive => &player.role,
+                None => continue,
+            };
+
+            if wins.iter().any(|w| w.user_id == player.user_id) {
+                continue;
+            }
+
+            if role.check_win(player.user_id, players, record) {
+                wins.push(SoloWin {
+                    user_id: player.user_id,
+                    role_id: role.id(),
+                    ends_game: role.win_ends_game(),
+                });
+            }
+        }
     }
+
+    (records, wins)
 }
 
 pub trait RoleExt {
@@ -238,3 +434,120 @@ impl RoleExt for Box<dyn crate::types::Role> {
         (**self).as_any().downcast_ref::<T>()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players(roles: &[RoleId]) -> Vec<Player> {
+        roles
+            .iter()
+            .enumerate()
+            .map(|(i, role)| {
+                let id = i as u64 + 1;
+                Player::new(
+                    UserId::new(id),
+                    format!("Người chơi {}", id),
+                    create_role(*role),
+                    String::new(),
+                )
+            })
+            .collect()
+    }
+
+    fn vote(players: &mut [Player], voter: u64, target: u64) {
+        players[voter as usize - 1]
+            .role
+            .set_vote_hanged(Some(target.to_string()));
+    }
+
+    fn influence(players: &mut [Player], wolffluence: u64, target: u64) {
+        players[wolffluence as usize - 1]
+            .role
+            .as_any_mut()
+            .downcast_mut::<Wolffluence>()
+            .unwrap()
+            .influence_player = Some(UserId::new(target));
+    }
+
+    #[test]
+    fn hang_needs_a_clear_majority_of_two() {
+        let mut players = players(&[RoleId::Villager; 4]);
+        vote(&mut players, 1, 3);
+        vote(&mut players, 2, 3);
+        vote(&mut players, 3, 4);
+        assert_eq!(process_vote(&mut players, None), Some((UserId::new(3), 2)));
+
+        let mut players = self::players(&[RoleId::Villager; 4]);
+        vote(&mut players, 1, 3);
+        vote(&mut players, 2, 4);
+        assert_eq!(process_vote(&mut players, None), None);
+    }
+
+    #[test]
+    fn mayor_counts_double_and_breaks_ties() {
+        let mayor = Some(UserId::new(1));
+
+        let mut players = players(&[RoleId::Villager; 5]);
+        vote(&mut players, 1, 4);
+        vote(&mut players, 2, 4);
+        vote(&mut players, 3, 5);
+        vote(&mut players, 5, 4);
+        assert_eq!(process_vote(&mut players, mayor), Some((UserId::new(4), 4)));
+
+        let mut players = self::players(&[RoleId::Villager; 5]);
+        vote(&mut players, 1, 4);
+        vote(&mut players, 2, 4);
+        vote(&mut players, 3, 2);
+        vote(&mut players, 4, 2);
+        vote(&mut players, 5, 2);
+        assert_eq!(process_vote(&mut players, mayor), Some((UserId::new(4), 3)));
+    }
+
+    #[test]
+    fn lone_mayor_vote_does_not_hang() {
+        let mut players = players(&[RoleId::Villager; 4]);
+        vote(&mut players, 1, 3);
+
+        assert_eq!(process_vote(&mut players, Some(UserId::new(1))), None);
+    }
+
+    #[test]
+    fn wolffluence_takes_over_the_influenced_vote() {
+        let mut players = players(&[
+            RoleId::Wolffluence,
+            RoleId::Villager,
+            RoleId::Villager,
+            RoleId::Villager,
+        ]);
+        influence(&mut players, 1, 2);
+        vote(&mut players, 1, 3);
+        vote(&mut players, 2, 4);
+        vote(&mut players, 4, 3);
+
+        assert_eq!(process_vote(&mut players, None), Some((UserId::new(3), 3)));
+    }
+
+    #[test]
+    fn judgment_applies_mayor_and_wolffluence_weights() {
+        let mut players = players(&[
+            RoleId::Wolffluence,
+            RoleId::Villager,
+            RoleId::Villager,
+            RoleId::Villager,
+        ]);
+        influence(&mut players, 1, 2);
+        let votes = HashMap::from([
+            (UserId::new(1), false),
+            (UserId::new(2), false),
+            (UserId::new(3), true),
+            (UserId::new(4), true),
+        ]);
+
+        assert_eq!(process_judgment(&players, &votes, None), (2, 2));
+        assert_eq!(
+            process_judgment(&players, &votes, Some(UserId::new(3))),
+            (3, 2)
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use serenity::all::{
+    ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, Timestamp, UserId,
+};
+
+use crate::constants::MAX_PLAYERS;
+use crate::game::RoomStatus;
+use crate::types::Player;
+
+/// Những gì cần để vẽ tin nhắn phòng chờ.
+pub struct LobbyView<'a> {
+    pub host_id: UserId,
+    pub players: &'a [Player],
+    pub status: &'a RoomStatus,
+    /// `None` nếu phòng không bật kiểm tra sẵn sàng.
+    pub ready: Option<&'a [UserId]>,
+    /// Thời điểm (unix giây) phòng sẽ tự bắt đầu, nếu đang đếm ngược.
+    pub auto_start_at: Option<i64>,
+}
+
+/// Embed phòng chờ, được sửa lại mỗi khi có người vào / rời phòng.
+pub fn lobby_embed(view: &LobbyView) -> CreateEmbed {
+    let (color, state) = match view.status {
+        RoomStatus::Waiting => (0x3498db, "Đang chờ"),
+        RoomStatus::Starting => (0xe67e22, "Đang chơi"),
+        RoomStatus::Ended => (0x95a5a6, "Đã đóng"),
+    };
+
+    let player_list = if view.players.is_empty() {
+        "```📭 Chưa có người chơi nào```".to_string()
+    } else {
+        view.players
+            .iter()
+            .enumerate()
+            .map(|(index, p)| {
+                let crown = if p.user_id == view.host_id {
+                    " 👑"
+                } else {
+                    ""
+                };
+                let ready = match view.ready {
+                    Some(ready) if ready.contains(&p.user_id) => " ✅",
+                    Some(_) => " ⏳",
+                    None => "",
+                };
+                format!("`{:02}` **{}**{}{}", index + 1, p.name, crown, ready)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut embed = CreateEmbed::new()
+        .color(color)
+        .title("🎮 PHÒNG CHƠI MA SÓI")
+        .description(player_list)
+        .field("👑 Chủ Phòng", format!("<@{}>", view.host_id), true)
+        .field(
+            "👥 Số Người Chơi",
+            format!("{}/{}", view.players.len(), MAX_PLAYERS),
+            true,
+        )
+        .field("⌛ Trạng Thái", state, true);
+
+    if *view.status == RoomStatus::Waiting {
+        if let Some(ready) = view.ready {
+            embed = embed.field(
+                "✋ Sẵn Sàng",
+                format!("{}/{}", ready.len(), view.players.len()),
+                true,
+            );
+        }
+        if let Some(at) = view.auto_start_at {
+            embed = embed.field("⏱️ Tự Bắt Đầu", format!("<t:{}:R>", at), true);
+        }
+    }
+
+    embed
+        .footer(CreateEmbedFooter::new(
+            "💡 Bấm nút bên dưới hoặc dùng /masoi-join để tham gia phòng",
+        ))
+        .timestamp(Timestamp::now())
+}
+
+/// Các nút của phòng chờ, chỉ hiển thị khi phòng còn đang chờ người chơi.
+pub fn lobby_components(view: &LobbyView) -> Vec<CreateActionRow> {
+    if *view.status != RoomStatus::Waiting {
+        return Vec::new();
+    }
+
+    let mut buttons = vec![
+        CreateButton::new("lobby_join")
+            .label("✅ Tham gia")
+            .style(ButtonStyle::Success),
+        CreateButton::new("lobby_leave")
+            .label("🚪 Rời phòng")
+            .style(ButtonStyle::Secondary),
+    ];
+    if view.ready.is_some() {
+        buttons.push(
+            CreateButton::new("lobby_ready")
+                .label("✋ Sẵn sàng")
+                .style(ButtonStyle::Secondary),
+        );
+    }
+    buttons.push(
+        CreateButton::new("lobby_start")
+            .label("▶️ Bắt đầu")
+            .style(ButtonStyle::Primary),
+    );
+
+    vec![CreateActionRow::Buttons(buttons)]
+}
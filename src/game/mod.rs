@@ -1,10 +1,24 @@
+pub mod afk;
 pub mod canvas;
+pub mod events;
+pub mod export;
 pub mod faction;
 pub mod helper;
+pub mod lobby;
+pub mod notifier;
+pub mod persistence;
 pub mod phases;
+pub mod rating;
+pub mod recap;
 pub mod room;
+pub mod simulation;
 pub mod state;
+pub mod substitution;
 
+#[cfg(test)]
+mod tests;
+
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::oneshot;
 
@@ -12,7 +26,10 @@ pub use room::RoomRegistry;
 use serenity::all::{ChannelId, MessageId, UserId};
 pub use state::GameState;
 
+use crate::constants::DEFAULT_AFK_THRESHOLD;
+use crate::db::ServerSettings;
 use crate::types::Player;
+use crate::utils::role::RoleId;
 
 #[derive(Debug, Clone)]
 pub struct ChatFile {
@@ -33,6 +50,21 @@ pub enum RoomStatus {
     Ended,
 }
 
+/// Người xem ván chơi. Khán giả không nằm trong `PlayerRegistry` nên không thể tác động vào game.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spectator {
+    pub user_id: UserId,
+    /// Được xem vai trò, chat của Sói và hành động ban đêm của mọi người.
+    pub full_view: bool,
+}
+
+/// Thời gian còn lại (giây) của các bộ đếm khi chủ phòng tạm dừng ván chơi.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PausedTimers {
+    pub phase_remaining: u64,
+    pub wolf_remaining: Option<u64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RoomSnapshot {
     pub status: RoomStatus,
@@ -58,6 +90,30 @@ pub enum LeaveResult {
     GameStarted,
 }
 
+#[derive(Debug)]
+pub enum SpectateResult {
+    Joined,
+    Left,
+    IsPlayer,
+    GameEnded,
+}
+
+/// Kết quả một thao tác của chủ phòng: `Done` kèm thông báo công khai, `Rejected` kèm lý do.
+#[derive(Debug)]
+pub enum HostActionResult {
+    Done(String),
+    Rejected(String),
+}
+
+#[derive(Debug)]
+pub enum ReadyResult {
+    Ready,
+    Unready,
+    NotJoined,
+    /// Phòng không bật kiểm tra sẵn sàng hoặc trò chơi đã bắt đầu.
+    Unavailable,
+}
+
 #[derive(Debug)]
 pub enum StartGameResult {
     Success,
@@ -96,17 +152,76 @@ pub enum RoomEvent {
     StatusRequest {
         reply: oneshot::Sender<RoomSnapshot>,
     },
+    /// Tin nhắn phòng chờ trong kênh, sẽ được cập nhật khi danh sách người chơi thay đổi.
+    SetLobbyMessage {
+        message_id: MessageId,
+    },
+    /// Bật / tắt trạng thái sẵn sàng của người chơi trong phòng chờ.
+    ToggleReady {
+        user_id: UserId,
+        reply: oneshot::Sender<ReadyResult>,
+    },
+    /// Hết giờ đếm ngược, phòng tự bắt đầu với vai trò mặc định.
+    AutoStartTimeout,
+    /// Phòng chờ không có hoạt động quá lâu và sẽ bị đóng.
+    LobbyIdleTimeout,
+    /// Bắt đầu xem phòng, hoặc thôi xem nếu đang là khán giả.
+    SpectateRequest {
+        user_id: UserId,
+        full_view: bool,
+        reply: oneshot::Sender<SpectateResult>,
+    },
 
     StartGame {
         user_id: UserId,
         custom_roles: Option<HashMap<u8, u8>>,
         reply: oneshot::Sender<StartGameResult>,
     },
+    /// Huỷ ván chơi ngay lập tức (chủ phòng hoặc quản trị viên).
     EndGame,
+    KickPlayer {
+        target: UserId,
+        reply: oneshot::Sender<HostActionResult>,
+    },
+    TransferHost {
+        target: UserId,
+        reply: oneshot::Sender<HostActionResult>,
+    },
+    /// Tạm dừng các bộ đếm giờ, hoặc tiếp tục nếu đang tạm dừng.
+    TogglePause {
+        reply: oneshot::Sender<HostActionResult>,
+    },
+    SkipPhase {
+        reply: oneshot::Sender<HostActionResult>,
+    },
+    /// Chủ phòng hoặc chính người chơi nhường ghế `seat` cho người khác vào thay.
+    OfferSeat {
+        requester: UserId,
+        seat: UserId,
+        reply: oneshot::Sender<HostActionResult>,
+    },
+    /// Một người chưa tham gia nhận ghế đang được nhường.
+    ClaimSeat {
+        seat: UserId,
+        user_id: UserId,
+        name: String,
+        avatar_url: String,
+        reply: oneshot::Sender<HostActionResult>,
+    },
 
     PhaseTimeout,
     VoteComplete,
 
+    MayorVote {
+        user_id: UserId,
+        target: UserId,
+    },
+    /// Thị Trưởng đã chết trao danh hiệu cho người kế nhiệm.
+    MayorSuccessor {
+        user_id: UserId,
+        target: UserId,
+    },
+
     WolfVote {
         user_id: UserId,
         target: UserId,
@@ -150,21 +265,84 @@ pub enum RoomEvent {
         target2: UserId,
         target3: UserId,
     },
+    PuppeteerTarget {
+        user_id: UserId,
+        target: UserId,
+    },
+    StalkerKill {
+        user_id: UserId,
+        target: UserId,
+    },
     HangVote {
         user_id: UserId,
         target: String,
     },
+    Accuse {
+        user_id: UserId,
+        target: UserId,
+    },
+    JudgmentVote {
+        user_id: UserId,
+        guilty: bool,
+    },
     GetAllPlayers {
         reply: tokio::sync::oneshot::Sender<Vec<Player>>,
     },
 }
 
-#[derive(Clone)]
+impl RoomEvent {
+    /// Người chơi thực hiện hành động của event, dùng để theo dõi ai đang AFK.
+    pub fn actor(&self) -> Option<UserId> {
+        match self {
+            RoomEvent::WolfVote { user_id, .. }
+            | RoomEvent::HangVote { user_id, .. }
+            | RoomEvent::JudgmentVote { user_id, .. }
+            | RoomEvent::SeerView { user_id, .. }
+            | RoomEvent::WitchPoison { user_id, .. }
+            | RoomEvent::WitchHeal { user_id, .. }
+            | RoomEvent::BodyguardProtect { user_id, .. }
+            | RoomEvent::DetectiveInvestigate { user_id, .. }
+            | RoomEvent::FoxSpiritFind { user_id, .. }
+            | RoomEvent::PuppeteerTarget { user_id, .. }
+            | RoomEvent::StalkerKill { user_id, .. } => Some(*user_id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoomSettings {
     pub wolf_vote_time: u64,
     pub night_time: u64,
     pub discuss_time: u64,
     pub vote_time: u64,
+    pub banned_roles: Vec<RoleId>,
+    /// Xếp vai sao cho rating trung bình của phe Sói và phe Dân gần nhau.
+    pub balanced_shuffle: bool,
+    pub ready_check: bool,
+    pub auto_start_time: u64,
+    /// Số lượt bỏ lỡ liên tiếp trước khi bị cảnh báo, 0 là tắt.
+    pub afk_threshold: u32,
+    pub mayor_election: bool,
+    pub classic_day: bool,
+}
+
+impl From<ServerSettings> for RoomSettings {
+    fn from(settings: ServerSettings) -> Self {
+        Self {
+            wolf_vote_time: settings.wolf_vote_time,
+            night_time: settings.night_time,
+            discuss_time: settings.discuss_time,
+            vote_time: settings.vote_time,
+            banned_roles: settings.banned_roles,
+            balanced_shuffle: settings.balanced_shuffle,
+            ready_check: settings.ready_check,
+            auto_start_time: settings.auto_start_time,
+            afk_threshold: settings.afk_threshold,
+            mayor_election: settings.mayor_election,
+            classic_day: settings.classic_day,
+        }
+    }
 }
 
 impl Default for RoomSettings {
@@ -174,6 +352,13 @@ impl Default for RoomSettings {
             night_time: 70,
             discuss_time: 90,
             vote_time: 30,
+            banned_roles: Vec::new(),
+            balanced_shuffle: false,
+            ready_check: false,
+            auto_start_time: 0,
+            afk_threshold: DEFAULT_AFK_THRESHOLD,
+            mayor_election: false,
+            classic_day: false,
         }
     }
 }
@@ -0,0 +1,286 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use serenity::all::{
+    ActionRowComponent, ButtonKind, ChannelId, CreateActionRow, CreateButton, CreateMessage,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditMessage, Http, MessageId,
+    UserId,
+};
+use serenity::async_trait;
+
+/// Đầu ra của ván chơi. `GameRoom` và các phase chỉ giao tiếp với người chơi qua trait này.
+#[async_trait]
+pub trait GameNotifier: Send + Sync {
+    /// Gửi tin nhắn riêng cho người chơi, trả về vị trí tin nhắn để có thể vô hiệu hoá sau.
+    async fn send_dm(
+        &self,
+        user_id: UserId,
+        message: CreateMessage,
+    ) -> Result<(ChannelId, MessageId)>;
+
+    /// Gửi tin nhắn vào một kênh chung.
+    async fn send_channel(
+        &self,
+        channel_id: ChannelId,
+        message: CreateMessage,
+    ) -> Result<(ChannelId, MessageId)>;
+
+    /// Vô hiệu hoá các nút bấm / menu của một tin nhắn đã gửi.
+    async fn disable_components(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()>;
+
+    /// Sửa lại nội dung một tin nhắn đã gửi.
+    async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: EditMessage,
+    ) -> Result<()>;
+}
+
+impl dyn GameNotifier {
+    /// Gửi tin nhắn văn bản đơn giản cho người chơi.
+    pub async fn say(&self, user_id: UserId, content: impl Into<String>) -> Result<()> {
+        self.send_dm(user_id, CreateMessage::new().content(content.into()))
+            .await
+            .map(|_| ())
+    }
+}
+
+pub struct DiscordNotifier {
+    http: Arc<Http>,
+}
+
+impl DiscordNotifier {
+    pub fn new(http: Arc<Http>) -> Self {
+        Self { http }
+    }
+}
+
+#[async_trait]
+impl GameNotifier for DiscordNotifier {
+    async fn send_dm(
+        &self,
+        user_id: UserId,
+        message: CreateMessage,
+    ) -> Result<(ChannelId, MessageId)> {
+        let dm = user_id.create_dm_channel(&self.http).await?;
+        let msg = dm.send_message(&self.http, message).await?;
+        Ok((msg.channel_id, msg.id))
+    }
+
+    async fn send_channel(
+        &self,
+        channel_id: ChannelId,
+        message: CreateMessage,
+    ) -> Result<(ChannelId, MessageId)> {
+        let msg = channel_id.send_message(&self.http, message).await?;
+        Ok((msg.channel_id, msg.id))
+    }
+
+    async fn disable_components(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()> {
+        let msg = channel_id.message(&self.http, message_id).await?;
+
+        let mut new_rows = Vec::new();
+        for row in msg.components {
+            let mut buttons = Vec::new();
+            let mut select_menu: Option<CreateSelectMenu> = None;
+
+            for component in row.components {
+                match component {
+                    ActionRowComponent::Button(b) => {
+                        if let ButtonKind::NonLink { custom_id, style } = &b.data {
+                            let mut new_btn = CreateButton::new(custom_id.clone())
+                                .style(*style)
+                                .disabled(true);
+
+                            if let Some(label) = &b.label {
+                                new_btn = new_btn.label(label.clone());
+                            }
+                            if let Some(emoji) = &b.emoji {
+                                new_btn = new_btn.emoji(emoji.clone());
+                            }
+
+                            buttons.push(new_btn);
+                        }
+                    }
+
+                    ActionRowComponent::SelectMenu(m) => {
+                        let dummy_option = CreateSelectMenuOption::new("Đã hết giờ", "expired")
+                            .emoji('⌛')
+                            .default_selection(true);
+
+                        let new_menu = CreateSelectMenu::new(
+                            m.custom_id.clone().unwrap_or_default(),
+                            CreateSelectMenuKind::String {
+                                options: vec![dummy_option],
+                            },
+                        )
+                        .placeholder("⌛ Đã hết thời gian chọn")
+                        .disabled(true);
+
+                        select_menu = Some(new_menu);
+                    }
+
+                    _ => {}
+                }
+            }
+
+            if !buttons.is_empty() {
+                new_rows.push(CreateActionRow::Buttons(buttons));
+            } else if let Some(menu) = select_menu {
+                new_rows.push(CreateActionRow::SelectMenu(menu));
+            }
+        }
+
+        let edit = EditMessage::new().content(msg.content).components(new_rows);
+        channel_id
+            .edit_message(&self.http, message_id, edit)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: EditMessage,
+    ) -> Result<()> {
+        channel_id
+            .edit_message(&self.http, message_id, message)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotifyTarget {
+    Dm(UserId),
+    Channel(ChannelId),
+}
+
+/// Một tin nhắn đã được `RecordingNotifier` ghi lại.
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    pub target: NotifyTarget,
+    pub channel_id: ChannelId,
+    pub message_id: MessageId,
+    pub content: String,
+    pub payload: serde_json::Value,
+    pub disabled: bool,
+}
+
+/// Notifier lưu mọi tin nhắn trong bộ nhớ, dùng để chạy ván chơi không cần Discord.
+#[derive(Clone, Default)]
+pub struct RecordingNotifier {
+    messages: Arc<Mutex<Vec<RecordedMessage>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl RecordingNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn messages(&self) -> Vec<RecordedMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    pub fn messages_for(&self, user_id: UserId) -> Vec<RecordedMessage> {
+        self.messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| m.target == NotifyTarget::Dm(user_id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear(&self) {
+        self.messages.lock().unwrap().clear();
+    }
+
+    fn record(
+        &self,
+        target: NotifyTarget,
+        channel_id: ChannelId,
+        message: &CreateMessage,
+    ) -> MessageId {
+        let message_id = MessageId::new(self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let payload = serde_json::to_value(message).unwrap_or_default();
+        let content = payload
+            .get("content")
+            .and_then(|c| c.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        self.messages.lock().unwrap().push(RecordedMessage {
+            target,
+            channel_id,
+            message_id,
+            content,
+            payload,
+            disabled: false,
+        });
+
+        message_id
+    }
+}
+
+#[async_trait]
+impl GameNotifier for RecordingNotifier {
+    async fn send_dm(
+        &self,
+        user_id: UserId,
+        message: CreateMessage,
+    ) -> Result<(ChannelId, MessageId)> {
+        let channel_id = ChannelId::new(user_id.get());
+        let message_id = self.record(NotifyTarget::Dm(user_id), channel_id, &message);
+        Ok((channel_id, message_id))
+    }
+
+    async fn send_channel(
+        &self,
+        channel_id: ChannelId,
+        message: CreateMessage,
+    ) -> Result<(ChannelId, MessageId)> {
+        let message_id = self.record(NotifyTarget::Channel(channel_id), channel_id, &message);
+        Ok((channel_id, message_id))
+    }
+
+    async fn disable_components(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()> {
+        if let Some(msg) = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|m| m.channel_id == channel_id && m.message_id == message_id)
+        {
+            msg.disabled = true;
+        }
+        Ok(())
+    }
+
+    async fn edit_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        message: EditMessage,
+    ) -> Result<()> {
+        let payload = serde_json::to_value(&message).unwrap_or_default();
+        if let Some(msg) = self
+            .messages
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|m| m.channel_id == channel_id && m.message_id == message_id)
+        {
+            if let Some(content) = payload.get("content").and_then(|c| c.as_str()) {
+                msg.content = content.to_string();
+            }
+            msg.payload = payload;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId, MessageId, UserId};
+use std::collections::HashMap;
+
+use crate::game::{GameState, PausedTimers, RoomSettings, Spectator};
+use crate::roles::{load_role_state, save_role_state};
+use crate::types::Player;
+use crate::utils::role::RoleId;
+
+pub type MessageMap = HashMap<UserId, Vec<(ChannelId, MessageId)>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub name: String,
+    pub user_id: UserId,
+    pub alive: bool,
+    pub voted: bool,
+    pub role_id: RoleId,
+    pub role_state: serde_json::Value,
+    pub can_use_skill: bool,
+    pub can_vote: bool,
+    pub can_chat: bool,
+    pub avatar_url: String,
+}
+
+impl SavedPlayer {
+    pub fn from_player(player: &Player) -> serde_json::Result<Self> {
+        Ok(Self {
+            name: player.name.clone(),
+            user_id: player.user_id,
+            alive: player.alive,
+            voted: player.voted,
+            role_id: player.role.id(),
+            role_state: save_role_state(player.role.as_ref())?,
+            can_use_skill: player.can_use_skill,
+            can_vote: player.can_vote,
+            can_chat: player.can_chat,
+            avatar_url: player.avatar_url.clone(),
+        })
+    }
+
+    pub fn into_player(self) -> serde_json::Result<Player> {
+        let role = load_role_state(self.role_id, self.role_state)?;
+        let mut player = Player::new(self.user_id, self.name, role, self.avatar_url);
+        player.alive = self.alive;
+        player.voted = self.voted;
+        player.can_use_skill = self.can_use_skill;
+        player.can_vote = self.can_vote;
+        player.can_chat = self.can_chat;
+        Ok(player)
+    }
+}
+
+/// Snapshot của một phòng đang chơi, được ghi lại sau mỗi lần chuyển phase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedRoom {
+    pub guild_id: GuildId,
+    pub host_id: UserId,
+    pub channel_id: ChannelId,
+    pub players: Vec<SavedPlayer>,
+    #[serde(default)]
+    pub spectators: Vec<Spectator>,
+    pub game_state: GameState,
+    pub settings: RoomSettings,
+    pub night_messages: MessageMap,
+    pub wolf_messages: MessageMap,
+    pub day_messages: MessageMap,
+    pub vote_messages: MessageMap,
+    /// Thời điểm (unix giây) phase hiện tại hết giờ.
+    pub phase_deadline: Option<i64>,
+    /// Thời điểm (unix giây) Sói hết giờ bỏ phiếu, `None` nếu đã chốt.
+    pub wolf_deadline: Option<i64>,
+    /// Có giá trị nếu ván đang bị chủ phòng tạm dừng.
+    #[serde(default)]
+    pub paused: Option<PausedTimers>,
+    #[serde(default)]
+    pub lobby_message: Option<MessageId>,
+    #[serde(default)]
+    pub afk_strikes: HashMap<UserId, u32>,
+    #[serde(default)]
+    pub active_players: Vec<UserId>,
+    #[serde(default)]
+    pub open_seats: Vec<UserId>,
+    #[serde(default)]
+    pub full_viewers: Vec<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::{create_role, Witch};
+
+    fn round_trip(player: &Player) -> Player {
+        let saved = SavedPlayer::from_player(player).unwrap();
+        let json = serde_json::to_string(&saved).unwrap();
+        serde_json::from_str::<SavedPlayer>(&json)
+            .unwrap()
+            .into_player()
+            .unwrap()
+    }
+
+    #[test]
+    fn every_role_state_survives_a_snapshot() {
+        for id in 0..=22 {
+            let role_id = RoleId::from_u8(id).unwrap();
+            let player = Player::new(
+                UserId::new(1),
+                "Người chơi".to_string(),
+                create_role(role_id),
+                String::new(),
+            );
+
+            let restored = round_trip(&player);
+
+            assert_eq!(restored.role.id(), role_id);
+            assert_eq!(
+                save_role_state(restored.role.as_ref()).unwrap(),
+                save_role_state(player.role.as_ref()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_used_abilities_and_player_flags() {
+        let mut player = Player::new(
+            UserId::new(1),
+            "Phù Thuỷ".to_string(),
+            create_role(RoleId::Witch),
+            String::new(),
+        );
+        let witch = player.role.as_any_mut().downcast_mut::<Witch>().unwrap();
+        witch.heal_count = 0;
+        witch.poisoned_person = Some(UserId::new(2));
+        player.can_vote = false;
+
+        let restored = round_trip(&player);
+
+        let witch = restored.role.as_any().downcast_ref::<Witch>().unwrap();
+        assert_eq!(witch.heal_count, 0);
+        assert_eq!(witch.poisoned_person, Some(UserId::new(2)));
+        assert!(!restored.can_vote);
+    }
+}
@@ -1,5 +1,226 @@
 use anyhow::Result;
+use chrono::Utc;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, CreateMessage, Timestamp};
 
-pub async fn execute_check_end_game() -> Result<bool> {
-    Ok(false)
+use crate::db::{GameRecord, GameRecordPlayer, PlayerGameRecord};
+use crate::game::events::GameEventKind;
+use crate::game::helper::{check_victory, player_won, SoloWin, VictoryResult, Winner};
+use crate::game::recap::build_recap_pages;
+use crate::game::room::GameRoom;
+use crate::roles::Dead;
+use crate::types::Player;
+use crate::utils::role::RoleId;
+
+pub async fn execute_check_end_game(room: &mut GameRoom) -> Result<Option<VictoryResult>> {
+    let victory = match check_victory(&room.players, &room.game_state.solo_wins) {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    let embed = build_result_embed(&room.players, &victory, room.game_state.night_count);
+
+    if let Err(e) = room
+        .notifier
+        .send_channel(
+            room.channel_id,
+            CreateMessage::new().add_embed(embed.clone()),
+        )
+        .await
+    {
+        tracing::error!("Lỗi gửi kết quả vào kênh {}: {:?}", room.channel_id, e);
+    }
+
+    for player in room.players.iter() {
+        let _ = room
+            .notifier
+            .send_dm(
+                player.user_id,
+                CreateMessage::new().add_embed(embed.clone()),
+            )
+            .await;
+    }
+
+    room.notify_spectators(CreateMessage::new().add_embed(embed), false)
+        .await;
+
+    for page in build_recap_pages(&room.players, &room.game_state.events) {
+        if let Err(e) = room
+            .notifier
+            .send_channel(room.channel_id, CreateMessage::new().embeds(page))
+            .await
+        {
+            tracing::error!(
+                "Lỗi gửi diễn biến ván chơi vào kênh {}: {:?}",
+                room.channel_id,
+                e
+            );
+        }
+    }
+
+    Ok(Some(victory))
+}
+
+/// Ghi nhận và thông báo những người chơi phe solo vừa đạt điều kiện thắng riêng.
+pub async fn announce_solo_wins(room: &mut GameRoom, wins: &[SoloWin]) {
+    for win in wins.iter() {
+        if room
+            .game_state
+            .solo_wins
+            .iter()
+            .any(|w| w.user_id == win.user_id)
+        {
+            continue;
+        }
+        room.game_state.solo_wins.push(win.clone());
+
+        let name = room
+            .players
+            .iter()
+            .find(|p| p.user_id == win.user_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+
+        let message = format!(
+            "🎭 **{}** ({}) đã đạt được điều kiện thắng riêng!",
+            name,
+            win.role_id.name()
+        );
+
+        room.game_state.record(GameEventKind::SoloWin {
+            user_id: win.user_id,
+            role_id: win.role_id,
+        });
+
+        for player in room.players.iter() {
+            let _ = room
+                .notifier
+                .send_dm(
+                    player.user_id,
+                    CreateMessage::new().content(message.clone()),
+                )
+                .await;
+        }
+        room.notify_spectators(CreateMessage::new().content(message), false)
+            .await;
+    }
+}
+
+/// Kết quả của từng người chơi trong ván vừa kết thúc, dùng để lưu thống kê.
+pub fn build_game_records(room: &GameRoom, victory: &VictoryResult) -> Vec<PlayerGameRecord> {
+    let finished_at = Utc::now().timestamp_millis();
+
+    room.players
+        .iter()
+        .map(|player| PlayerGameRecord {
+            id: None,
+            guild_id: room.guild_id.to_string(),
+            user_id: player.user_id.to_string(),
+            name: player.name.clone(),
+            role_id: original_role_id(player),
+            faction: player.role.faction(),
+            won: player_won(player, victory),
+            survived: player.alive,
+            death_cause: player
+                .role
+                .as_any()
+                .downcast_ref::<Dead>()
+                .and_then(|dead| dead.death_cause),
+            night_count: room.game_state.night_count,
+            finished_at,
+        })
+        .collect()
+}
+
+/// Biên bản đầy đủ của ván vừa kết thúc để xem lại bằng /masoi-history.
+pub fn build_game_record(room: &GameRoom, victory: &VictoryResult) -> GameRecord {
+    GameRecord {
+        id: None,
+        guild_id: room.guild_id.to_string(),
+        winner: victory.faction,
+        solo_winners: victory.solo_winners.clone(),
+        night_count: room.game_state.night_count,
+        players: room
+            .players
+            .iter()
+            .map(|player| GameRecordPlayer {
+                user_id: player.user_id,
+                name: player.name.clone(),
+                role_id: original_role_id(player),
+                faction: player.role.faction(),
+                won: player_won(player, victory),
+                survived: player.alive,
+                death_cause: player
+                    .role
+                    .as_any()
+                    .downcast_ref::<Dead>()
+                    .and_then(|dead| dead.death_cause),
+            })
+            .collect(),
+        events: room.game_state.events.clone(),
+        finished_at: Utc::now().timestamp_millis(),
+    }
+}
+
+/// Vai trò ban đầu của người chơi (người chết lấy từ `Dead::original_role_id`).
+pub fn original_role_id(player: &Player) -> RoleId {
+    player
+        .role
+        .as_any()
+        .downcast_ref::<Dead>()
+        .map(|dead| dead.original_role_id)
+        .unwrap_or_else(|| player.role.id())
+}
+
+fn build_result_embed(
+    players: &[Player],
+    victory: &VictoryResult,
+    night_count: i32,
+) -> CreateEmbed {
+    let (color, title) = match victory.winner {
+        Winner::Werewolf => (0xe74c3c, "🐺 MA SÓI CHIẾN THẮNG"),
+        Winner::Village => (0x2ecc71, "🏡 DÂN LÀNG CHIẾN THẮNG"),
+        Winner::Solo => (0x9b59b6, "🎪 PHE SOLO CHIẾN THẮNG"),
+    };
+
+    let player_list = players
+        .iter()
+        .enumerate()
+        .map(|(index, p)| {
+            let status_icon = if p.alive { "🟢" } else { "💀" };
+            format!(
+                "`{:02}` {} **{}** — {}",
+                index + 1,
+                status_icon,
+                p.name,
+                original_role_id(p).name()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut description = format!(
+        "🏆 Phe chiến thắng: **{}**\n🌙 Số đêm đã qua: **{}**",
+        victory.faction.name(),
+        night_count
+    );
+
+    if !victory.solo_winners.is_empty() {
+        let names = players
+            .iter()
+            .filter(|p| victory.solo_winners.contains(&p.user_id))
+            .map(|p| format!("**{}** ({})", p.name, original_role_id(p).name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        description.push_str(&format!("\n🎭 Thắng riêng: {}", names));
+    }
+
+    CreateEmbed::new()
+        .color(color)
+        .title(format!("🏁 TRÒ CHƠI KẾT THÚC — {}", title))
+        .description(description)
+        .field("📋 Vai Trò Của Người Chơi", player_list, false)
+        .footer(CreateEmbedFooter::new(
+            "🔄 Sử dụng /masoi-create để tạo phòng mới",
+        ))
+        .timestamp(Timestamp::now())
 }
@@ -14,6 +14,7 @@ use crate::{
 
 pub async fn execute_day_phase(room: &mut GameRoom) -> Result<()> {
     room.night_messages.clear();
+    room.game_state.accusations.clear();
 
     let canvas_players: Vec<PlayerInfo> = room
         .players
@@ -34,7 +35,7 @@ pub async fn execute_day_phase(room: &mut GameRoom) -> Result<()> {
         .color(0x00ae86)
         .image("attachment://players.png");
 
-    let http = room.http.clone();
+    let notifier = room.notifier.clone();
     let mut set = JoinSet::new();
 
     for player in room.players.iter() {
@@ -42,7 +43,7 @@ pub async fn execute_day_phase(room: &mut GameRoom) -> Result<()> {
         let role_id = player.role.id();
         let can_use_skill = player.can_use_skill;
 
-        let (prompt, components) = build_day_prompt_and_components(
+        let (prompt, mut components) = build_day_prompt_and_components(
             room,
             user_id,
             role_id,
@@ -50,18 +51,25 @@ pub async fn execute_day_phase(room: &mut GameRoom) -> Result<()> {
             room.game_state.night_count,
         )?;
 
-        let http = http.clone();
+        if room.settings.classic_day && player.alive && player.can_vote {
+            components.push(CreateActionRow::Buttons(vec![CreateButton::new(format!(
+                "accuse_req_{}",
+                user_id
+            ))
+            .label("☝️ Tố cáo")
+            .style(ButtonStyle::Danger)]));
+        }
+
+        let notifier = notifier.clone();
         let embed = embed_template.clone();
         let image_data = image_data.clone();
 
         set.spawn(async move {
-            let dm = user_id.create_dm_channel(&http).await?;
-
             let attachment = CreateAttachment::bytes(image_data, "players.png");
 
-            let msg = dm
-                .send_message(
-                    &http,
+            let (channel_id, message_id) = notifier
+                .send_dm(
+                    user_id,
                     CreateMessage::new()
                         .content(prompt)
                         .add_embed(embed)
@@ -70,7 +78,7 @@ pub async fn execute_day_phase(room: &mut GameRoom) -> Result<()> {
                 )
                 .await?;
 
-            Ok::<(UserId, ChannelId, MessageId), serenity::Error>((user_id, msg.channel_id, msg.id))
+            Ok::<(UserId, ChannelId, MessageId), anyhow::Error>((user_id, channel_id, message_id))
         });
     }
 
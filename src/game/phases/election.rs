@@ -0,0 +1,206 @@
+use anyhow::Result;
+use rand::seq::SliceRandom;
+use serenity::all::{
+    CreateActionRow, CreateMessage, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    UserId,
+};
+
+use crate::game::events::GameEventKind;
+use crate::game::room::GameRoom;
+
+/// Gửi phiếu bầu Thị Trưởng cho mọi người chơi còn sống.
+pub async fn execute_election_phase(room: &mut GameRoom) -> Result<()> {
+    room.game_state.election_votes.clear();
+
+    let prompt = "🎖️ **Bầu Thị Trưởng!**\nPhiếu treo cổ của Thị Trưởng được tính gấp đôi và quyết định khi hoà phiếu. Hãy chọn người bạn tin tưởng:";
+
+    let alive: Vec<UserId> = room
+        .players
+        .iter()
+        .filter(|p| p.alive)
+        .map(|p| p.user_id)
+        .collect();
+
+    for user_id in alive {
+        let menu = candidate_menu(room, "mayor_vote", "🎖️ Chọn Thị Trưởng...");
+        match room
+            .notifier
+            .send_dm(
+                user_id,
+                CreateMessage::new()
+                    .content(prompt)
+                    .components(vec![CreateActionRow::SelectMenu(menu)]),
+            )
+            .await
+        {
+            Ok(message) => room.vote_messages.entry(user_id).or_default().push(message),
+            Err(e) => tracing::error!("Lỗi gửi phiếu bầu Thị Trưởng: {:?}", e),
+        }
+    }
+
+    room.notify_spectators(
+        CreateMessage::new().content("# 🎖️ Dân làng bắt đầu bầu Thị Trưởng."),
+        false,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Kiểm phiếu bầu Thị Trưởng; hoà phiếu thì chọn ngẫu nhiên trong số người dẫn đầu.
+pub async fn resolve_election(room: &mut GameRoom) -> Result<()> {
+    let votes = std::mem::take(&mut room.game_state.election_votes);
+
+    let mut counts: Vec<(UserId, usize)> = Vec::new();
+    for target in votes.values() {
+        match counts.iter_mut().find(|(id, _)| id == target) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*target, 1)),
+        }
+    }
+
+    let max_votes = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let leaders: Vec<UserId> = counts
+        .iter()
+        .filter(|(_, count)| *count == max_votes)
+        .map(|(id, _)| *id)
+        .collect();
+
+    let message = match leaders.choose(&mut rand::thread_rng()) {
+        Some(mayor) => {
+            room.game_state.mayor = Some(*mayor);
+            room.game_state
+                .record(GameEventKind::MayorElected { user_id: *mayor });
+            let tie_note = if leaders.len() > 1 {
+                " (bốc thăm vì hoà phiếu)"
+            } else {
+                ""
+            };
+            format!(
+                "🎖️ **{}** được bầu làm Thị Trưởng với **{}** phiếu{}!",
+                player_name(room, *mayor),
+                max_votes,
+                tie_note
+            )
+        }
+        None => "🎖️ Không ai bỏ phiếu, làng không có Thị Trưởng.".to_string(),
+    };
+
+    room.announce(&message).await;
+    Ok(())
+}
+
+/// Nếu Thị Trưởng vừa chết, mời họ chọn người kế nhiệm trong số người còn sống.
+pub async fn check_mayor_succession(room: &mut GameRoom) {
+    let Some(mayor) = room.game_state.mayor else {
+        return;
+    };
+    if room.players.iter().any(|p| p.user_id == mayor && p.alive) {
+        return;
+    }
+
+    room.game_state.mayor = None;
+    room.game_state.mayor_heir_pending = Some(mayor);
+
+    let menu = candidate_menu(room, "mayor_successor", "🎖️ Chọn người kế nhiệm...");
+    let _ = room
+        .notifier
+        .send_dm(
+            mayor,
+            CreateMessage::new()
+                .content("🎖️ Bạn đã chết khi đang là Thị Trưởng. Hãy trao lại danh hiệu cho một người còn sống:")
+                .components(vec![CreateActionRow::SelectMenu(menu)]),
+        )
+        .await;
+
+    room.announce(&format!(
+        "🎖️ Thị Trưởng **{}** đã chết và đang chọn người kế nhiệm.",
+        player_name(room, mayor)
+    ))
+    .await;
+}
+
+/// Thị Trưởng cũ không chọn người kế nhiệm trước khi chuyển phase (chết vì AFK, bỏ đi...):
+/// danh hiệu được trao ngẫu nhiên cho một người còn sống.
+pub async fn expire_mayor_succession(room: &mut GameRoom) {
+    let Some(old_mayor) = room.game_state.mayor_heir_pending.take() else {
+        return;
+    };
+
+    let alive: Vec<UserId> = room
+        .players
+        .iter()
+        .filter(|p| p.alive)
+        .map(|p| p.user_id)
+        .collect();
+    let Some(heir) = alive.choose(&mut rand::thread_rng()).copied() else {
+        return;
+    };
+
+    room.game_state.mayor = Some(heir);
+    room.game_state
+        .record(GameEventKind::MayorElected { user_id: heir });
+
+    room.announce(&format!(
+        "🎖️ **{}** không kịp chọn người kế nhiệm, danh hiệu Thị Trưởng được trao ngẫu nhiên cho **{}**.",
+        player_name(room, old_mayor),
+        player_name(room, heir)
+    ))
+    .await;
+}
+
+/// Thị Trưởng cũ trao danh hiệu cho `heir`, chỉ khi họ vẫn đang được chờ chọn người kế nhiệm.
+pub async fn pass_mayor_title(room: &mut GameRoom, user_id: UserId, heir: UserId) {
+    if room.game_state.mayor_heir_pending != Some(user_id) {
+        let _ = room
+            .notifier
+            .say(user_id, "❌ Bạn không còn quyền chọn người kế nhiệm.")
+            .await;
+        return;
+    }
+    if !room.players.iter().any(|p| p.user_id == heir && p.alive) {
+        let _ = room
+            .notifier
+            .say(user_id, "❌ Người này đã chết, hãy chọn người khác.")
+            .await;
+        return;
+    }
+
+    room.game_state.mayor_heir_pending = None;
+    room.game_state.mayor = Some(heir);
+    room.game_state
+        .record(GameEventKind::MayorElected { user_id: heir });
+
+    room.announce(&format!(
+        "🎖️ **{}** đã trao danh hiệu Thị Trưởng cho **{}**.",
+        player_name(room, user_id),
+        player_name(room, heir)
+    ))
+    .await;
+}
+
+fn candidate_menu(room: &GameRoom, custom_id: &str, placeholder: &str) -> CreateSelectMenu {
+    let options = room
+        .players
+        .iter()
+        .filter(|p| p.alive)
+        .enumerate()
+        .map(|(index, p)| {
+            CreateSelectMenuOption::new(format!("{}. {}", index + 1, p.name), p.user_id.to_string())
+                .emoji('👤')
+        })
+        .collect();
+
+    CreateSelectMenu::new(custom_id, CreateSelectMenuKind::String { options })
+        .placeholder(placeholder)
+        .min_values(1)
+        .max_values(1)
+}
+
+pub(crate) fn player_name(room: &GameRoom, user_id: UserId) -> String {
+    room.players
+        .iter()
+        .find(|p| p.user_id == user_id)
+        .map(|p| p.name.clone())
+        .unwrap_or_else(|| format!("<@{}>", user_id))
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateMessage, UserId};
+
+use crate::constants::DEFENSE_TIME;
+use crate::game::helper::{apply_deaths, process_judgment};
+use crate::game::phases::{announce_solo_wins, election::player_name};
+use crate::game::room::GameRoom;
+use crate::types::DeathCause;
+
+/// Người bị tố cáo nhiều nhất; hoà thì lấy lời tố cáo của Thị Trưởng nếu có, không thì không ai bị xét xử.
+pub fn pick_accused(room: &GameRoom) -> Option<UserId> {
+    let accusations = &room.game_state.accusations;
+
+    let mut counts: Vec<(UserId, usize)> = Vec::new();
+    for (accuser, target) in accusations.iter() {
+        let alive = |id: &UserId| room.players.iter().any(|p| p.user_id == *id && p.alive);
+        if !alive(accuser) || !alive(target) {
+            continue;
+        }
+        match counts.iter_mut().find(|(id, _)| id == target) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*target, 1)),
+        }
+    }
+
+    let max = counts.iter().map(|(_, count)| *count).max()?;
+    let leaders: Vec<UserId> = counts
+        .into_iter()
+        .filter(|(_, count)| *count == max)
+        .map(|(id, _)| id)
+        .collect();
+
+    match leaders.as_slice() {
+        [only] => Some(*only),
+        _ => room
+            .game_state
+            .mayor
+            .and_then(|mayor| accusations.get(&mayor))
+            .filter(|choice| leaders.contains(choice))
+            .copied(),
+    }
+}
+
+/// Đưa người bị tố cáo ra xét xử: chỉ họ được nói trong lúc bào chữa.
+pub async fn execute_defense_phase(room: &mut GameRoom) -> Result<()> {
+    let Some(accused) = room.game_state.accused else {
+        return Ok(());
+    };
+    let name = player_name(room, accused);
+
+    room.announce(&format!(
+        "⚖️ **{}** bị đưa ra xét xử! Họ có **{} giây** để tự bào chữa, mọi người khác phải im lặng.",
+        name, DEFENSE_TIME
+    ))
+    .await;
+
+    let _ = room
+        .notifier
+        .say(
+            accused,
+            "🎤 Đến lượt bạn bào chữa! Hãy nhắn tin để thuyết phục dân làng tha cho bạn.",
+        )
+        .await;
+
+    Ok(())
+}
+
+/// Gửi nút phán quyết cho những người còn quyền bỏ phiếu, trừ người bị xét xử.
+pub async fn execute_judgment_phase(room: &mut GameRoom) -> Result<()> {
+    room.game_state.judgment_votes.clear();
+
+    let Some(accused) = room.game_state.accused else {
+        return Ok(());
+    };
+    let name = player_name(room, accused);
+
+    let voters: Vec<UserId> = room
+        .players
+        .iter()
+        .filter(|p| p.alive && p.can_vote && p.user_id != accused)
+        .map(|p| p.user_id)
+        .collect();
+
+    let buttons = CreateActionRow::Buttons(vec![
+        CreateButton::new("judgment_guilty")
+            .label("👍 Treo cổ")
+            .style(ButtonStyle::Danger),
+        CreateButton::new("judgment_innocent")
+            .label("👎 Tha")
+            .style(ButtonStyle::Success),
+    ]);
+
+    for user_id in voters {
+        match room
+            .notifier
+            .send_dm(
+                user_id,
+                CreateMessage::new()
+                    .content(format!("⚖️ **Phán quyết!** Có treo cổ **{}** không?", name))
+                    .components(vec![buttons.clone()]),
+            )
+            .await
+        {
+            Ok(message) => room.vote_messages.entry(user_id).or_default().push(message),
+            Err(e) => tracing::error!("Lỗi gửi phiếu phán quyết: {:?}", e),
+        }
+    }
+
+    room.notify_spectators(
+        CreateMessage::new().content(format!("# ⚖️ Dân làng phán quyết số phận của {}.", name)),
+        false,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Chốt phán quyết: treo cổ nếu phiếu treo nhiều hơn phiếu tha, rồi kết thúc ngày.
+pub async fn execute_verdict(room: &mut GameRoom) -> Result<()> {
+    let night_count = room.game_state.night_count;
+    let accused = room.game_state.accused.take();
+    let votes = std::mem::take(&mut room.game_state.judgment_votes);
+    room.game_state.accusations.clear();
+
+    let mut solo_wins = Vec::new();
+
+    let summary = match accused {
+        Some(accused) => {
+            let name = player_name(room, accused);
+            let (guilty, innocent) = process_judgment(&room.players, &votes, room.game_state.mayor);
+
+            if guilty > innocent {
+                let (records, wins) = apply_deaths(
+                    &mut room.players,
+                    &[(accused, DeathCause::Hanged)],
+                    night_count,
+                );
+                room.game_state.record_deaths(&records);
+                solo_wins = wins;
+
+                format!(
+                    "⚖️ **{}** đã bị treo cổ với **{}** phiếu treo, **{}** phiếu tha.",
+                    name, guilty, innocent
+                )
+            } else {
+                format!(
+                    "⚖️ **{}** được tha với **{}** phiếu treo, **{}** phiếu tha.",
+                    name, guilty, innocent
+                )
+            }
+        }
+        None => "⚖️ Không có ai bị đưa ra xét xử hôm nay.".to_string(),
+    };
+
+    for player in room.players.iter_mut() {
+        player.role.set_vote_hanged(None);
+        player.reset_round();
+    }
+
+    room.announce(&summary).await;
+
+    announce_solo_wins(room, &solo_wins).await;
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 pub mod check_end;
 pub mod day;
+pub mod election;
+pub mod judgment;
 pub mod night;
 pub mod solve;
 pub mod vote;
@@ -7,6 +9,8 @@ pub mod vote;
 // Re-exports
 pub use check_end::*;
 pub use day::*;
+pub use election::*;
+pub use judgment::*;
 pub use night::*;
 pub use solve::*;
 pub use vote::*;
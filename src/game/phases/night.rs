@@ -42,7 +42,7 @@ pub async fn execute_night_phase(room: &mut GameRoom) -> Result<()> {
         .color(0x00ae86)
         .image("attachment://players.png");
 
-    let http = room.http.clone();
+    let notifier = room.notifier.clone();
     let night_title = night_title.to_string();
 
     let mut set = JoinSet::new();
@@ -56,25 +56,24 @@ pub async fn execute_night_phase(room: &mut GameRoom) -> Result<()> {
         let build_result = build_night_prompt_and_components(room, user_id, role_id, can_use_skill);
 
         if let Ok((prompt, components)) = build_result {
-            let http = http.clone();
+            let notifier = notifier.clone();
             let image_data = image_data.clone();
             let embed_template = embed_template.clone();
             let night_title_clone = night_title.clone();
 
             set.spawn(async move {
-                let dm = user_id.create_dm_channel(&http).await?;
-
-                dm.send_message(
-                    &http,
-                    CreateMessage::new().content(format!("# 🌑 Đêm {}.", night_title_clone)),
-                )
-                .await?;
+                notifier
+                    .send_dm(
+                        user_id,
+                        CreateMessage::new().content(format!("# 🌑 Đêm {}.", night_title_clone)),
+                    )
+                    .await?;
 
                 let attachment = CreateAttachment::bytes(image_data, "players.png");
 
-                let msg = dm
-                    .send_message(
-                        &http,
+                let (channel_id, message_id) = notifier
+                    .send_dm(
+                        user_id,
                         CreateMessage::new()
                             .content(prompt)
                             .add_embed(embed_template)
@@ -83,11 +82,8 @@ pub async fn execute_night_phase(room: &mut GameRoom) -> Result<()> {
                     )
                     .await?;
 
-                Ok::<(UserId, ChannelId, MessageId, Faction), serenity::Error>((
-                    user_id,
-                    msg.channel_id,
-                    msg.id,
-                    faction,
+                Ok::<(UserId, ChannelId, MessageId, Faction), anyhow::Error>((
+                    user_id, channel_id, message_id, faction,
                 ))
             });
         }
@@ -1,9 +1,362 @@
 use anyhow::Result;
+use serenity::all::{CreateMessage, UserId};
 
+use crate::game::helper::{apply_deaths, player_has_acted, total_voted_wolves_solve, SoloWin};
+use crate::game::phases::announce_solo_wins;
+use crate::game::recap::build_recap_embeds;
 use crate::game::room::GameRoom;
+use crate::roles::{Bodyguard, Elder, Puppeteer, Stalker, Witch};
+use crate::types::{DeathCause, DeathRecord, Player};
+use crate::utils::role::RoleId;
+
+/// Kết quả của một đêm sau khi đã xử lý toàn bộ hành động.
+#[derive(Debug, Default, Clone)]
+pub struct NightOutcome {
+    pub deaths: Vec<DeathRecord>,
+    pub cursed_turned: Option<UserId>,
+    pub solo_wins: Vec<SoloWin>,
+}
 
 pub async fn execute_solve_phase(room: &mut GameRoom) -> Result<()> {
     tracing::info!("execute_solve_phase {:?}", room.game_state.phase);
 
+    let night_count = room.game_state.night_count;
+    let outcome = resolve_night(&mut room.players, night_count);
+
+    tracing::info!(
+        "Room {}: Night {} outcome {:?}",
+        room.guild_id,
+        night_count,
+        outcome
+    );
+
+    let summary = if outcome.deaths.is_empty() {
+        format!(
+            "# ☀️ Trời đã sáng (ngày {}).\nĐêm qua không có ai chết.",
+            night_count
+        )
+    } else {
+        let names = outcome
+            .deaths
+            .iter()
+            .filter_map(|d| room.players.iter().find(|p| p.user_id == d.user_id))
+            .map(|p| format!("**{}**", p.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "# ☀️ Trời đã sáng (ngày {}).\n💀 Đêm qua {} đã chết.",
+            night_count, names
+        )
+    };
+
+    room.game_state.record_deaths(&outcome.deaths);
+
+    for player in room.players.iter() {
+        let _ = room
+            .notifier
+            .send_dm(
+                player.user_id,
+                CreateMessage::new().content(summary.clone()),
+            )
+            .await;
+    }
+
+    room.notify_spectators(CreateMessage::new().content(summary), false)
+        .await;
+    let night_events: Vec<_> = room
+        .game_state
+        .events_in_night(night_count)
+        .cloned()
+        .collect();
+    room.notify_spectators(
+        CreateMessage::new().embeds(build_recap_embeds(&room.players, &night_events)),
+        true,
+    )
+    .await;
+
+    if let Some(cursed_id) = outcome.cursed_turned {
+        let _ = room
+            .notifier
+            .say(
+                cursed_id,
+                "🐺 Bạn đã bị sói cắn và trở thành **Ma Sói**. Từ đêm nay hãy đi săn cùng đàn!",
+            )
+            .await;
+    }
+
+    announce_solo_wins(room, &outcome.solo_wins).await;
+
     Ok(())
 }
+
+/// Xử lý toàn bộ hành động ban đêm theo thứ tự cố định.
+///
+/// Thứ tự: Người Múa Rối đổi mục tiêu của sói → Bảo Vệ đỡ đòn → Phù Thuỷ cứu →
+/// Già Làng mất một mạng → Bán Sói hoá sói → Phù Thuỷ đầu độc → Stalker ám sát.
+pub fn resolve_night(players: &mut [Player], night_count: i32) -> NightOutcome {
+    let mut outcome = NightOutcome::default();
+    let mut deaths: Vec<(UserId, DeathCause)> = Vec::new();
+
+    let is_alive =
+        |players: &[Player], id: UserId| players.iter().any(|p| p.user_id == id && p.alive);
+
+    let mut bite_target = total_voted_wolves_solve(players);
+
+    if let Some(forced) = players
+        .iter()
+        .filter(|p| p.alive)
+        .find_map(|p| p.role.as_any().downcast_ref::<Puppeteer>())
+        .and_then(|puppeteer| puppeteer.target_wolf)
+    {
+        bite_target = Some(forced);
+    }
+
+    let witch = players
+        .iter()
+        .filter(|p| p.alive)
+        .find_map(|p| {
+            p.role
+                .as_any()
+                .downcast_ref::<Witch>()
+                .map(|w| (p.user_id, w))
+        })
+        .map(|(id, w)| (id, w.healed_person, w.poisoned_person));
+
+    let stalker_kills: Vec<UserId> = players
+        .iter()
+        .filter(|p| p.alive)
+        .filter_map(|p| p.role.as_any().downcast_ref::<Stalker>())
+        .filter_map(|s| s.killed_person)
+        .filter(|target| {
+            players
+                .iter()
+                .find(|p| p.user_id == *target)
+                .is_some_and(|p| !player_has_acted(p))
+        })
+        .collect();
+
+    if let Some(target) = bite_target.filter(|id| is_alive(players, *id)) {
+        let guard_id = players
+            .iter()
+            .filter(|p| p.alive)
+            .find(|p| {
+                p.role
+                    .as_any()
+                    .downcast_ref::<Bodyguard>()
+                    .is_some_and(|b| p.user_id == target || b.protected_person == Some(target))
+            })
+            .map(|p| p.user_id);
+
+        let healed = witch.is_some_and(|(_, healed, _)| healed == Some(target));
+
+        if let Some(guard_id) = guard_id {
+            if let Some(guard) = players.iter_mut().find(|p| p.user_id == guard_id) {
+                if let Some(bodyguard) = guard.role.as_any_mut().downcast_mut::<Bodyguard>() {
+                    bodyguard.hp = bodyguard.hp.saturating_sub(1);
+                    if bodyguard.hp == 0 {
+                        deaths.push((guard_id, DeathCause::WolfBite));
+                    }
+                }
+            }
+        } else if healed {
+            if let Some((witch_id, _, _)) = witch {
+                if let Some(w) = players
+                    .iter_mut()
+                    .find(|p| p.user_id == witch_id)
+                    .and_then(|p| p.role.as_any_mut().downcast_mut::<Witch>())
+                {
+                    w.heal_count = w.heal_count.saturating_sub(1);
+                }
+            }
+        } else if let Some(victim) = players.iter_mut().find(|p| p.user_id == target) {
+            match victim.role.id() {
+                RoleId::Elder => {
+                    if let Some(elder) = victim.role.as_any_mut().downcast_mut::<Elder>() {
+                        elder.hp = elder.hp.saturating_sub(1);
+                        if elder.hp == 0 {
+                            deaths.push((target, DeathCause::WolfBite));
+                        }
+                    }
+                }
+                RoleId::Cursed => {
+                    victim.role = crate::roles::create_role(RoleId::Werewolf);
+                    outcome.cursed_turned = Some(target);
+                }
+                _ => deaths.push((target, DeathCause::WolfBite)),
+            }
+        }
+    }
+
+    if night_count > 1 {
+        if let Some((_, _, Some(poisoned))) = witch {
+            if is_alive(players, poisoned) && !deaths.iter().any(|(id, _)| *id == poisoned) {
+                deaths.push((poisoned, DeathCause::WitchPoison));
+            }
+        }
+    }
+
+    for target in stalker_kills {
+        if is_alive(players, target) && !deaths.iter().any(|(id, _)| *id == target) {
+            deaths.push((target, DeathCause::StalkerKill));
+        }
+    }
+
+    let (records, solo_wins) = apply_deaths(players, &deaths, night_count);
+
+    for player in players.iter_mut() {
+        player.role.reset_day();
+        player.reset_day();
+    }
+
+    outcome.deaths = records;
+    outcome.solo_wins = solo_wins;
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::{create_role, KittenWolf, Seer, VoodooWerewolf, Werewolf, Wolffluence};
+    use crate::types::Faction;
+
+    fn players(roles: &[RoleId]) -> Vec<Player> {
+        roles
+            .iter()
+            .enumerate()
+            .map(|(i, role)| {
+                let id = i as u64 + 1;
+                Player::new(
+                    UserId::new(id),
+                    format!("Người chơi {}", id),
+                    create_role(*role),
+                    String::new(),
+                )
+            })
+            .collect()
+    }
+
+    fn role_mut<T: 'static>(players: &mut [Player], id: u64) -> &mut T {
+        players
+            .iter_mut()
+            .find(|p| p.user_id == UserId::new(id))
+            .and_then(|p| p.role.as_any_mut().downcast_mut::<T>())
+            .unwrap()
+    }
+
+    fn bite(players: &mut [Player], wolf: u64, target: u64) {
+        let target = Some(UserId::new(target));
+        let role = players[wolf as usize - 1].role.as_any_mut();
+        if let Some(werewolf) = role.downcast_mut::<Werewolf>() {
+            werewolf.vote_bite = target;
+        } else if let Some(kitten) = role.downcast_mut::<KittenWolf>() {
+            kitten.vote_bite = target;
+        } else if let Some(voodoo) = role.downcast_mut::<VoodooWerewolf>() {
+            voodoo.vote_bite = target;
+        } else if let Some(wolffluence) = role.downcast_mut::<Wolffluence>() {
+            wolffluence.vote_bite = target;
+        } else {
+            panic!("người chơi {} không phải sói biết cắn", wolf);
+        }
+    }
+
+    fn deaths(outcome: &NightOutcome) -> Vec<(u64, DeathCause)> {
+        outcome
+            .deaths
+            .iter()
+            .map(|d| (d.user_id.get(), d.cause))
+            .collect()
+    }
+
+    #[test]
+    fn every_wolf_role_bites() {
+        for wolf in [
+            RoleId::Werewolf,
+            RoleId::KittenWolf,
+            RoleId::Voodoo,
+            RoleId::Wolffluence,
+        ] {
+            let mut players = players(&[wolf, RoleId::Villager, RoleId::Villager]);
+            bite(&mut players, 1, 2);
+
+            let outcome = resolve_night(&mut players, 1);
+
+            assert_eq!(
+                deaths(&outcome),
+                vec![(2, DeathCause::WolfBite)],
+                "{:?}",
+                wolf
+            );
+        }
+    }
+
+    #[test]
+    fn bodyguard_takes_bites_until_out_of_hp() {
+        let mut players = players(&[RoleId::Werewolf, RoleId::Bodyguard, RoleId::Villager]);
+
+        role_mut::<Bodyguard>(&mut players, 2).protected_person = Some(UserId::new(3));
+        bite(&mut players, 1, 3);
+        let outcome = resolve_night(&mut players, 1);
+        assert!(outcome.deaths.is_empty());
+        assert_eq!(role_mut::<Bodyguard>(&mut players, 2).hp, 1);
+
+        role_mut::<Bodyguard>(&mut players, 2).protected_person = Some(UserId::new(3));
+        bite(&mut players, 1, 3);
+        let outcome = resolve_night(&mut players, 2);
+        assert_eq!(deaths(&outcome), vec![(2, DeathCause::WolfBite)]);
+        assert!(players[2].alive);
+    }
+
+    #[test]
+    fn elder_survives_the_first_bite() {
+        let mut players = players(&[RoleId::Werewolf, RoleId::Elder, RoleId::Villager]);
+
+        bite(&mut players, 1, 2);
+        assert!(resolve_night(&mut players, 1).deaths.is_empty());
+
+        bite(&mut players, 1, 2);
+        let outcome = resolve_night(&mut players, 2);
+        assert_eq!(deaths(&outcome), vec![(2, DeathCause::WolfBite)]);
+    }
+
+    #[test]
+    fn bitten_cursed_turns_into_a_wolf() {
+        let mut players = players(&[RoleId::Werewolf, RoleId::Cursed, RoleId::Villager]);
+
+        bite(&mut players, 1, 2);
+        let outcome = resolve_night(&mut players, 1);
+
+        assert!(outcome.deaths.is_empty());
+        assert_eq!(outcome.cursed_turned, Some(UserId::new(2)));
+        assert_eq!(players[1].role.faction(), Faction::Werewolf);
+    }
+
+    #[test]
+    fn witch_cannot_poison_on_the_first_night() {
+        let mut players = players(&[RoleId::Werewolf, RoleId::Witch, RoleId::Villager]);
+
+        role_mut::<Witch>(&mut players, 2).poisoned_person = Some(UserId::new(3));
+        assert!(resolve_night(&mut players, 1).deaths.is_empty());
+
+        role_mut::<Witch>(&mut players, 2).poisoned_person = Some(UserId::new(3));
+        let outcome = resolve_night(&mut players, 2);
+        assert_eq!(deaths(&outcome), vec![(3, DeathCause::WitchPoison)]);
+    }
+
+    #[test]
+    fn stalker_only_kills_targets_who_stayed_idle() {
+        let mut players = players(&[
+            RoleId::Werewolf,
+            RoleId::Stalker,
+            RoleId::Villager,
+            RoleId::Seer,
+        ]);
+
+        role_mut::<Seer>(&mut players, 4).view_count = 0;
+        role_mut::<Stalker>(&mut players, 2).killed_person = Some(UserId::new(4));
+        assert!(resolve_night(&mut players, 1).deaths.is_empty());
+
+        role_mut::<Stalker>(&mut players, 2).killed_person = Some(UserId::new(3));
+        let outcome = resolve_night(&mut players, 2);
+        assert_eq!(deaths(&outcome), vec![(3, DeathCause::StalkerKill)]);
+    }
+}
@@ -8,8 +8,13 @@ use serenity::all::{
 use tokio::task::JoinSet;
 
 use crate::{
-    game::{canvas::create_avatar_collage, room::GameRoom},
-    types::player::PlayerInfo,
+    game::phases::announce_solo_wins,
+    game::{
+        canvas::create_avatar_collage,
+        helper::{apply_deaths, process_vote},
+        room::GameRoom,
+    },
+    types::{player::PlayerInfo, DeathCause},
 };
 
 pub async fn execute_vote_phase(room: &mut GameRoom) -> Result<()> {
@@ -33,7 +38,7 @@ pub async fn execute_vote_phase(room: &mut GameRoom) -> Result<()> {
         .color(0x00ae86)
         .image("attachment://players.png");
 
-    let http = room.http.clone();
+    let notifier = room.notifier.clone();
     let mut set = JoinSet::new();
 
     for player in room.players.iter() {
@@ -43,18 +48,16 @@ pub async fn execute_vote_phase(room: &mut GameRoom) -> Result<()> {
 
         let (prompt, components) = build_vote_prompt_and_components(user_id, is_alive, can_vote)?;
 
-        let http = http.clone();
+        let notifier = notifier.clone();
         let embed = embed_template.clone();
         let image = shared_image.clone();
 
         set.spawn(async move {
-            let dm = user_id.create_dm_channel(&http).await?;
-
             let attachment = CreateAttachment::bytes(image.as_ref().clone(), "players.png");
 
-            let msg = dm
-                .send_message(
-                    &http,
+            let (channel_id, message_id) = notifier
+                .send_dm(
+                    user_id,
                     CreateMessage::new()
                         .content(prompt)
                         .add_embed(embed)
@@ -63,7 +66,7 @@ pub async fn execute_vote_phase(room: &mut GameRoom) -> Result<()> {
                 )
                 .await?;
 
-            Ok::<(UserId, ChannelId, MessageId), serenity::Error>((user_id, msg.channel_id, msg.id))
+            Ok::<(UserId, ChannelId, MessageId), anyhow::Error>((user_id, channel_id, message_id))
         });
     }
 
@@ -83,6 +86,60 @@ pub async fn execute_vote_phase(room: &mut GameRoom) -> Result<()> {
     Ok(())
 }
 
+pub async fn execute_hang_phase(room: &mut GameRoom) -> Result<()> {
+    let night_count = room.game_state.night_count;
+
+    let mut solo_wins = Vec::new();
+
+    let mayor = room.game_state.mayor;
+    let summary = match process_vote(&mut room.players, mayor) {
+        Some((hanged_id, votes)) => {
+            let name = room
+                .players
+                .iter()
+                .find(|p| p.user_id == hanged_id)
+                .map(|p| p.name.clone())
+                .unwrap_or_default();
+
+            let (records, wins) = apply_deaths(
+                &mut room.players,
+                &[(hanged_id, DeathCause::Hanged)],
+                night_count,
+            );
+            room.game_state.record_deaths(&records);
+            solo_wins = wins;
+
+            format!(
+                "⚖️ **{}** đã bị dân làng treo cổ với **{}** phiếu.",
+                name, votes
+            )
+        }
+        None => "⚖️ Không có ai bị treo cổ hôm nay.".to_string(),
+    };
+
+    for player in room.players.iter_mut() {
+        player.role.set_vote_hanged(None);
+        player.reset_round();
+    }
+
+    for player in room.players.iter() {
+        let _ = room
+            .notifier
+            .send_dm(
+                player.user_id,
+                CreateMessage::new().content(summary.clone()),
+            )
+            .await;
+    }
+
+    room.notify_spectators(CreateMessage::new().content(summary), false)
+        .await;
+
+    announce_solo_wins(room, &solo_wins).await;
+
+    Ok(())
+}
+
 fn build_vote_prompt_and_components(
     owner_id: UserId,
     is_alive: bool,
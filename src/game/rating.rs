@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serenity::all::UserId;
+
+use crate::types::Faction;
+use crate::utils::role::RoleId;
+
+pub const DEFAULT_RATING: f64 = 1000.0;
+
+const K_FACTOR: f64 = 32.0;
+
+/// Mỗi điểm `RoleId::weight()` nghiêng về một phe được quy đổi thành bấy nhiêu điểm rating.
+const WEIGHT_TO_RATING: f64 = 15.0;
+
+/// Số lần xáo thử khi chia vai cân bằng.
+const BALANCE_ATTEMPTS: usize = 200;
+
+/// Một người chơi trong ván vừa kết thúc, dùng để tính lại rating.
+#[derive(Debug, Clone)]
+pub struct RatedPlayer {
+    pub user_id: UserId,
+    pub faction: Faction,
+    pub won: bool,
+}
+
+fn same_team(a: &RatedPlayer, b: &RatedPlayer) -> bool {
+    match (a.faction, b.faction) {
+        (Faction::Solo, _) | (_, Faction::Solo) => a.user_id == b.user_id,
+        (Faction::Werewolf, Faction::Werewolf) => true,
+        (Faction::Werewolf, _) | (_, Faction::Werewolf) => false,
+        _ => true,
+    }
+}
+
+/// Tính rating mới theo Elo.
+///
+/// Mỗi người được so với rating trung bình của đối thủ. Độ mạnh của phe được cộng vào
+/// từ `balance`, là tổng `RoleId::weight()` của đội hình (dương là lợi cho Dân Làng).
+pub fn compute_ratings(
+    players: &[RatedPlayer],
+    ratings: &HashMap<UserId, f64>,
+    balance: i32,
+) -> HashMap<UserId, f64> {
+    let rating_of = |user_id: UserId| ratings.get(&user_id).copied().unwrap_or(DEFAULT_RATING);
+
+    players
+        .iter()
+        .map(|player| {
+            let own = rating_of(player.user_id);
+
+            let opponents: Vec<f64> = players
+                .iter()
+                .filter(|other| !same_team(player, other))
+                .map(|other| rating_of(other.user_id))
+                .collect();
+            let opponent_avg = if opponents.is_empty() {
+                own
+            } else {
+                opponents.iter().sum::<f64>() / opponents.len() as f64
+            };
+
+            let handicap = match player.faction {
+                Faction::Werewolf => -(balance as f64) * WEIGHT_TO_RATING,
+                Faction::Solo => 0.0,
+                _ => balance as f64 * WEIGHT_TO_RATING,
+            };
+
+            let expected = 1.0 / (1.0 + 10f64.powf((opponent_avg - own - handicap) / 400.0));
+            let score = if player.won { 1.0 } else { 0.0 };
+
+            (player.user_id, own + K_FACTOR * (score - expected))
+        })
+        .collect()
+}
+
+/// Xáo `pool` nhiều lần và giữ cách xếp mà rating trung bình của phe Sói và phe còn lại
+/// gần nhau nhất. `ratings[i]` là rating của người sẽ nhận `pool[i]`.
+pub fn balanced_shuffle(pool: &mut [RoleId], ratings: &[f64], rng: &mut impl Rng) {
+    let spread = |pool: &[RoleId]| {
+        let (mut wolf_sum, mut wolf_count, mut other_sum, mut other_count) = (0.0, 0, 0.0, 0);
+        for (role, rating) in pool.iter().zip(ratings.iter()) {
+            if role.is_werewolf() {
+                wolf_sum += rating;
+                wolf_count += 1;
+            } else {
+                other_sum += rating;
+                other_count += 1;
+            }
+        }
+        if wolf_count == 0 || other_count == 0 {
+            return 0.0;
+        }
+        (wolf_sum / wolf_count as f64 - other_sum / other_count as f64).abs()
+    };
+
+    pool.shuffle(rng);
+    let mut best = pool.to_vec();
+    let mut best_spread = spread(&best);
+
+    for _ in 0..BALANCE_ATTEMPTS {
+        pool.shuffle(rng);
+        let current = spread(pool);
+        if current < best_spread {
+            best_spread = current;
+            best.copy_from_slice(pool);
+        }
+    }
+
+    pool.copy_from_slice(&best);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn rated(id: u64, faction: Faction, won: bool) -> RatedPlayer {
+        RatedPlayer {
+            user_id: UserId::new(id),
+            faction,
+            won,
+        }
+    }
+
+    #[test]
+    fn winners_gain_what_losers_lose_in_an_even_game() {
+        let players = [
+            rated(1, Faction::Werewolf, true),
+            rated(2, Faction::Village, false),
+        ];
+
+        let ratings = compute_ratings(&players, &HashMap::new(), 0);
+
+        let gain = ratings[&UserId::new(1)] - DEFAULT_RATING;
+        let loss = DEFAULT_RATING - ratings[&UserId::new(2)];
+        assert!((gain - K_FACTOR / 2.0).abs() < 1e-9);
+        assert!((gain - loss).abs() < 1e-9);
+    }
+
+    #[test]
+    fn beating_a_favoured_side_is_worth_more() {
+        let players = [
+            rated(1, Faction::Werewolf, true),
+            rated(2, Faction::Village, false),
+        ];
+
+        let even = compute_ratings(&players, &HashMap::new(), 0);
+        let village_favoured = compute_ratings(&players, &HashMap::new(), 5);
+
+        assert!(village_favoured[&UserId::new(1)] > even[&UserId::new(1)]);
+    }
+
+    #[test]
+    fn balanced_shuffle_keeps_roles_and_evens_out_ratings() {
+        let mut pool = vec![
+            RoleId::Werewolf,
+            RoleId::Villager,
+            RoleId::Villager,
+            RoleId::Villager,
+        ];
+        let ratings = [1400.0, 1000.0, 1000.0, 600.0];
+
+        balanced_shuffle(&mut pool, &ratings, &mut StdRng::seed_from_u64(7));
+
+        let mut sorted = pool.clone();
+        sorted.sort_by_key(|role| *role as u8);
+        assert_eq!(
+            sorted,
+            vec![
+                RoleId::Werewolf,
+                RoleId::Villager,
+                RoleId::Villager,
+                RoleId::Villager
+            ]
+        );
+        let wolf_seat = pool.iter().position(|role| role.is_werewolf()).unwrap();
+        assert!(wolf_seat == 1 || wolf_seat == 2);
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::BTreeMap;
+
+use serenity::all::{CreateEmbed, UserId};
+
+use crate::game::events::{GameEvent, GameEventKind};
+use crate::game::state::Phase;
+use crate::types::{Faction, Player};
+
+/// Discord cho phép tối đa 10 embed trong một tin nhắn.
+const EMBEDS_PER_MESSAGE: usize = 10;
+
+/// Tổng số ký tự của mọi embed trong một tin nhắn không được vượt quá 6000.
+const CHARS_PER_MESSAGE: usize = 6000;
+
+/// Số ký tự tối đa của phần mô tả mỗi embed, Discord cho phép 4096.
+const MAX_DESCRIPTION_LEN: usize = 4000;
+
+#[derive(Default)]
+struct NightRecap {
+    /// Phiếu cắn cuối cùng của mỗi con sói.
+    wolf_votes: BTreeMap<UserId, UserId>,
+    night_lines: Vec<String>,
+    night_deaths: Vec<String>,
+    /// Phiếu treo cổ cuối cùng của mỗi người (`None` là bỏ qua).
+    hang_votes: BTreeMap<UserId, Option<UserId>>,
+    accusations: Vec<String>,
+    /// Phiếu phán quyết cuối cùng của mỗi người (`true` là treo cổ).
+    judgment_votes: BTreeMap<UserId, bool>,
+    day_lines: Vec<String>,
+}
+
+/// Diễn biến của một đêm và ngày tiếp theo, mỗi dòng là một sự việc.
+pub struct NightSummary {
+    pub night: i32,
+    pub night_lines: Vec<String>,
+    /// Rỗng nếu ván kết thúc ngay trong đêm.
+    pub day_lines: Vec<String>,
+}
+
+/// Dựng các embed tóm tắt diễn biến từng đêm / ngày từ nhật ký sự kiện.
+pub fn build_recap_embeds(players: &[Player], events: &[GameEvent]) -> Vec<CreateEmbed> {
+    recap_sections(players, events)
+        .into_iter()
+        .map(|(title, description)| recap_embed(title, description))
+        .collect()
+}
+
+/// Chia diễn biến thành từng trang, mỗi trang gửi được trong một tin nhắn:
+/// tối đa 10 embed và tổng số ký tự không quá 6000.
+pub fn build_recap_pages(players: &[Player], events: &[GameEvent]) -> Vec<Vec<CreateEmbed>> {
+    let mut pages: Vec<Vec<CreateEmbed>> = Vec::new();
+    let mut page = Vec::new();
+    let mut page_chars = 0;
+
+    for (title, description) in recap_sections(players, events) {
+        let chars = title.chars().count() + description.chars().count();
+        if !page.is_empty()
+            && (page.len() >= EMBEDS_PER_MESSAGE || page_chars + chars > CHARS_PER_MESSAGE)
+        {
+            pages.push(std::mem::take(&mut page));
+            page_chars = 0;
+        }
+        page.push(recap_embed(title, description));
+        page_chars += chars;
+    }
+
+    if !page.is_empty() {
+        pages.push(page);
+    }
+    pages
+}
+
+fn recap_embed(title: String, description: String) -> CreateEmbed {
+    CreateEmbed::new()
+        .color(0x34495e)
+        .title(title)
+        .description(description)
+}
+
+/// Tiêu đề và nội dung tóm tắt của từng đêm.
+fn recap_sections(players: &[Player], events: &[GameEvent]) -> Vec<(String, String)> {
+    let name = |user_id: UserId| {
+        players
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .map(|p| format!("**{}**", p.name))
+            .unwrap_or_else(|| format!("<@{}>", user_id))
+    };
+
+    summarize_nights(events, &name)
+        .into_iter()
+        .map(|summary| {
+            let mut sections = vec![format!(
+                "**🌙 Ban đêm**\n{}",
+                summary.night_lines.join("\n")
+            )];
+            if !summary.day_lines.is_empty() {
+                sections.push(format!("**☀️ Ban ngày**\n{}", summary.day_lines.join("\n")));
+            }
+
+            let mut description = sections.join("\n\n");
+            if let Some((cut, _)) = description.char_indices().nth(MAX_DESCRIPTION_LEN) {
+                description.truncate(cut);
+                description.push('…');
+            }
+
+            (format!("📜 DIỄN BIẾN ĐÊM {}", summary.night), description)
+        })
+        .collect()
+}
+
+/// Gom nhật ký sự kiện thành diễn biến từng đêm, `name` quyết định cách hiển thị người chơi.
+pub fn summarize_nights(
+    events: &[GameEvent],
+    name: &impl Fn(UserId) -> String,
+) -> Vec<NightSummary> {
+    let mut nights: BTreeMap<i32, NightRecap> = BTreeMap::new();
+    let mut phase = Phase::Waiting;
+
+    for event in events.iter().filter(|e| e.night > 0) {
+        let recap = nights.entry(event.night).or_default();
+
+        match &event.kind {
+            GameEventKind::PhaseChanged { phase: next } => phase = next.clone(),
+            GameEventKind::WolfVote { voter, target } => {
+                recap.wolf_votes.insert(*voter, *target);
+            }
+            GameEventKind::SeerView {
+                seer,
+                target,
+                result,
+            } => recap.night_lines.push(format!(
+                "👁️ Tiên Tri {} soi {} → **{}**",
+                name(*seer),
+                name(*target),
+                faction_label(*result)
+            )),
+            GameEventKind::DetectiveCheck {
+                detective,
+                targets,
+                same_faction,
+            } => recap.night_lines.push(format!(
+                "🔎 Thám Tử {} điều tra {} và {} → **{}**",
+                name(*detective),
+                name(targets.0),
+                name(targets.1),
+                if *same_faction {
+                    "cùng phe"
+                } else {
+                    "khác phe"
+                }
+            )),
+            GameEventKind::Protect { bodyguard, target } => recap.night_lines.push(format!(
+                "🛡️ Bảo Vệ {} bảo vệ {}",
+                name(*bodyguard),
+                name(*target)
+            )),
+            GameEventKind::Heal { witch, target } => recap.night_lines.push(format!(
+                "💫 Phù Thuỷ {} cứu {}",
+                name(*witch),
+                name(*target)
+            )),
+            GameEventKind::Poison { witch, target } => recap.night_lines.push(format!(
+                "💊 Phù Thuỷ {} đầu độc {}",
+                name(*witch),
+                name(*target)
+            )),
+            GameEventKind::HangVote { voter, target } => {
+                recap.hang_votes.insert(*voter, *target);
+            }
+            GameEventKind::Accusation { accuser, target } => {
+                recap
+                    .accusations
+                    .push(format!("☝️ {} tố cáo {}", name(*accuser), name(*target)))
+            }
+            GameEventKind::JudgmentVote { voter, guilty } => {
+                recap.judgment_votes.insert(*voter, *guilty);
+            }
+            GameEventKind::Death { user_id, cause } => {
+                let line = format!("💀 {} chết ({})", name(*user_id), cause.name());
+                if phase == Phase::Night {
+                    recap.night_deaths.push(line);
+                } else {
+                    recap.day_lines.push(line);
+                }
+            }
+            GameEventKind::SoloWin { user_id, role_id } => recap.day_lines.push(format!(
+                "🎭 {} ({}) đạt điều kiện thắng riêng",
+                name(*user_id),
+                role_id.name()
+            )),
+            GameEventKind::MayorElected { user_id } => recap
+                .day_lines
+                .push(format!("🎖️ {} trở thành Thị Trưởng", name(*user_id))),
+            GameEventKind::RoleAssigned { .. } | GameEventKind::GameEnded { .. } => {}
+        }
+    }
+
+    nights
+        .into_iter()
+        .map(|(night, recap)| {
+            let mut night_lines = Vec::new();
+            if !recap.wolf_votes.is_empty() {
+                night_lines.push(format!(
+                    "🐺 Sói bỏ phiếu: {}",
+                    tally(recap.wolf_votes.values().map(|t| Some(*t)), name)
+                ));
+            }
+            night_lines.extend(recap.night_lines);
+            if recap.night_deaths.is_empty() {
+                night_lines.push("🌅 Không có ai chết trong đêm.".to_string());
+            } else {
+                night_lines.extend(recap.night_deaths);
+            }
+
+            let mut day_lines = Vec::new();
+            if !recap.hang_votes.is_empty() {
+                day_lines.push(format!(
+                    "🗳️ Phiếu treo cổ: {}",
+                    tally(recap.hang_votes.values().copied(), name)
+                ));
+                for (voter, target) in recap.hang_votes.iter() {
+                    day_lines.push(format!(
+                        "> {} → {}",
+                        name(*voter),
+                        target.map(name).unwrap_or_else(|| "bỏ qua".to_string())
+                    ));
+                }
+            }
+            day_lines.extend(recap.accusations);
+            if !recap.judgment_votes.is_empty() {
+                let guilty = recap.judgment_votes.values().filter(|g| **g).count();
+                day_lines.push(format!(
+                    "⚖️ Phán quyết: treo cổ ({}), tha ({})",
+                    guilty,
+                    recap.judgment_votes.len() - guilty
+                ));
+                for (voter, guilty) in recap.judgment_votes.iter() {
+                    day_lines.push(format!(
+                        "> {} → {}",
+                        name(*voter),
+                        if *guilty { "treo cổ" } else { "tha" }
+                    ));
+                }
+            }
+            day_lines.extend(recap.day_lines);
+
+            NightSummary {
+                night,
+                night_lines,
+                day_lines,
+            }
+        })
+        .collect()
+}
+
+/// Gom phiếu theo mục tiêu, ví dụ "**A** (2), bỏ qua (1)".
+fn tally(votes: impl Iterator<Item = Option<UserId>>, name: &impl Fn(UserId) -> String) -> String {
+    let mut counts: Vec<(Option<UserId>, usize)> = Vec::new();
+    for vote in votes {
+        match counts.iter_mut().find(|(target, _)| *target == vote) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((vote, 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    counts
+        .into_iter()
+        .map(|(target, count)| {
+            let label = target.map(name).unwrap_or_else(|| "bỏ qua".to_string());
+            format!("{} ({})", label, count)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn faction_label(faction: Faction) -> &'static str {
+    match faction {
+        Faction::Werewolf => "Ma Sói",
+        _ => "Dân Làng",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roles::create_role;
+    use crate::utils::role::RoleId;
+
+    fn players() -> Vec<Player> {
+        [(1, "Nguyễn Thị Ánh Tuyết"), (2, "Trần Đức Phước")]
+            .into_iter()
+            .map(|(id, name)| {
+                Player::new(
+                    UserId::new(id),
+                    name.to_string(),
+                    create_role(RoleId::Villager),
+                    String::new(),
+                )
+            })
+            .collect()
+    }
+
+    /// `views` lượt soi của Tiên Tri trong mỗi đêm từ 1 đến `nights`.
+    fn seer_nights(nights: i32, views: usize) -> Vec<GameEvent> {
+        (1..=nights)
+            .flat_map(|night| {
+                (0..views).map(move |_| {
+                    GameEvent::new(
+                        night,
+                        GameEventKind::SeerView {
+                            seer: UserId::new(1),
+                            target: UserId::new(2),
+                            result: Faction::Village,
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn texts(page: &[CreateEmbed]) -> Vec<(String, String)> {
+        page.iter()
+            .map(|embed| {
+                let value = serde_json::to_value(embed).unwrap();
+                (
+                    value["title"].as_str().unwrap().to_string(),
+                    value["description"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn long_descriptions_are_cut_by_characters() {
+        let embeds = build_recap_embeds(&players(), &seer_nights(1, 200));
+
+        let (_, description) = texts(&embeds).remove(0);
+        assert_eq!(description.chars().count(), MAX_DESCRIPTION_LEN + 1);
+        assert!(description.ends_with('…'));
+    }
+
+    #[test]
+    fn pages_stay_within_discord_limits() {
+        for (nights, views) in [(25, 1), (6, 200), (12, 30)] {
+            let pages = build_recap_pages(&players(), &seer_nights(nights, views));
+
+            let embeds: usize = pages.iter().map(|page| page.len()).sum();
+            assert_eq!(embeds, nights as usize);
+            for page in pages.iter() {
+                let chars: usize = texts(page)
+                    .iter()
+                    .map(|(title, description)| title.chars().count() + description.chars().count())
+                    .sum();
+                assert!(!page.is_empty() && page.len() <= EMBEDS_PER_MESSAGE);
+                assert!(chars <= CHARS_PER_MESSAGE, "{} ký tự", chars);
+            }
+        }
+    }
+}
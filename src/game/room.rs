@@ -1,10 +1,10 @@
 use anyhow::Result;
+use chrono::Utc;
 use rand::seq::SliceRandom;
 use rand::{rngs::StdRng, SeedableRng};
 use serenity::all::{
-    ActionRowComponent, ChannelId, Context, CreateActionRow, CreateAttachment, CreateButton,
-    CreateMessage, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditMessage,
-    GuildId, Http, MessageId, UserId,
+    ChannelId, Context, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateMessage, EditMessage, GuildId, Http, MessageId, UserId,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -12,24 +12,56 @@ use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::task::JoinSet;
 use tokio::time::{sleep, Duration};
 
+use super::events::GameEventKind;
+use super::notifier::{DiscordNotifier, GameNotifier};
+use super::persistence::{SavedPlayer, SavedRoom};
 use super::state::Phase;
+use crate::bot::BotData;
+use crate::constants::{DEFENSE_TIME, JUDGMENT_TIME, LOBBY_IDLE_TIME, MAX_PLAYERS, MIN_PLAYERS};
+use crate::db::{HistoryRepository, RatingRepository, RoomRepository, StatsRepository};
+use crate::game::afk::{process_afk, requires_night_action, requires_vote};
+use crate::game::helper::{player_won, VictoryResult};
+use crate::game::lobby::{lobby_components, lobby_embed, LobbyView};
 use crate::game::phases::{
-    execute_day_phase, execute_night_phase, execute_solve_phase, execute_vote_phase,
+    build_game_record, build_game_records, check_mayor_succession, execute_check_end_game,
+    execute_day_phase, execute_defense_phase, execute_election_phase, execute_hang_phase,
+    execute_judgment_phase, execute_night_phase, execute_solve_phase, execute_verdict,
+    execute_vote_phase, expire_mayor_succession, original_role_id, pass_mayor_title, pick_accused,
+    resolve_election,
 };
+use crate::game::rating::{balanced_shuffle, compute_ratings, RatedPlayer, DEFAULT_RATING};
+use crate::game::substitution::transfer_seat;
 use crate::game::{
-    GameState, JoinResult, LeaveResult, MessageTypeStore, RawFile, RoomEvent, RoomSettings,
-    RoomSnapshot, RoomStatus, StartGameResult,
+    GameState, HostActionResult, JoinResult, LeaveResult, MessageTypeStore, PausedTimers, RawFile,
+    ReadyResult, RoomEvent, RoomSettings, RoomSnapshot, RoomStatus, SpectateResult, Spectator,
+    StartGameResult,
 };
 use crate::types::data::RolesData;
 use crate::types::{Faction, Player};
-use crate::utils::role::RoleId;
+use crate::utils::role::{generate_role_composition, RoleId};
+use crate::utils::role_validator::validate_composition;
 
-pub type RoomRegistry = Arc<RwLock<HashMap<GuildId, RoomHandle>>>;
-pub type PlayerRegistry = Arc<RwLock<HashMap<UserId, GuildId>>>;
+/// Mỗi kênh có tối đa một phòng, một server có thể có nhiều phòng ở các kênh khác nhau.
+pub type RoomRegistry = Arc<RwLock<HashMap<ChannelId, RoomHandle>>>;
+/// Kênh của phòng mà người chơi đang tham gia.
+pub type PlayerRegistry = Arc<RwLock<HashMap<UserId, ChannelId>>>;
 
 #[derive(Clone)]
 pub struct RoomHandle {
     pub sender: mpsc::UnboundedSender<RoomEvent>,
+    pub guild_id: GuildId,
+}
+
+/// Các kênh đang có phòng còn hoạt động trong server.
+pub fn guild_room_channels(
+    rooms: &HashMap<ChannelId, RoomHandle>,
+    guild_id: GuildId,
+) -> Vec<ChannelId> {
+    rooms
+        .iter()
+        .filter(|(_, handle)| handle.guild_id == guild_id && !handle.sender.is_closed())
+        .map(|(channel_id, _)| *channel_id)
+        .collect()
 }
 
 pub struct GameRoom {
@@ -37,11 +69,12 @@ pub struct GameRoom {
     pub(crate) host_id: UserId,
     pub(crate) channel_id: ChannelId,
     pub(crate) players: Vec<Player>,
+    pub(crate) spectators: Vec<Spectator>,
     status: RoomStatus,
     pub(crate) game_state: GameState,
     pub(crate) settings: RoomSettings,
     pub(crate) roles_json: RolesData,
-    pub(crate) http: Arc<Http>,
+    pub(crate) notifier: Arc<dyn GameNotifier>,
     pub(crate) http_client: reqwest::Client,
 
     pub(crate) night_messages:
@@ -53,11 +86,38 @@ pub struct GameRoom {
     pub(crate) vote_messages:
         HashMap<UserId, Vec<(serenity::all::ChannelId, serenity::all::MessageId)>>,
 
+    /// Số phase liên tiếp mỗi người bỏ lỡ hành động bắt buộc.
+    pub(crate) afk_strikes: HashMap<UserId, u32>,
+    /// Những người đã hành động trong phase hiện tại.
+    pub(crate) active_players: Vec<UserId>,
+    /// Phase bị chủ phòng bỏ qua thì không tính lượt bỏ lỡ.
+    pub(crate) afk_exempt: bool,
+    /// Các ghế đang được nhường, chờ người khác vào thay.
+    open_seats: Vec<UserId>,
+    /// Những người từng xem ván với toàn bộ thông tin, không được vào thay ghế.
+    full_viewers: Vec<UserId>,
+
     receiver: mpsc::UnboundedReceiver<RoomEvent>,
 
     sender: mpsc::UnboundedSender<RoomEvent>,
 
     phase_timer_cancel: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    wolf_timer: Option<tokio::task::JoinHandle<()>>,
+    phase_deadline: Option<i64>,
+    wolf_deadline: Option<i64>,
+    paused: Option<PausedTimers>,
+    lobby_message: Option<MessageId>,
+    ready_players: Vec<UserId>,
+    auto_start_timer: Option<tokio::task::JoinHandle<()>>,
+    auto_start_deadline: Option<i64>,
+    idle_timer: Option<tokio::task::JoinHandle<()>>,
+
+    room_registry: RoomRegistry,
+    player_registry: PlayerRegistry,
+    room_store: Option<RoomRepository>,
+    stats_store: Option<StatsRepository>,
+    rating_store: Option<RatingRepository>,
+    history_store: Option<HistoryRepository>,
 }
 
 impl GameRoom {
@@ -66,7 +126,9 @@ impl GameRoom {
         host_id: UserId,
         channel_id: ChannelId,
         roles_json: RolesData,
-        http: Arc<Http>,
+        notifier: Arc<dyn GameNotifier>,
+        room_registry: RoomRegistry,
+        player_registry: PlayerRegistry,
     ) -> (Self, RoomHandle) {
         let (sender, receiver) = mpsc::unbounded_channel();
 
@@ -74,9 +136,10 @@ impl GameRoom {
             guild_id,
             host_id,
             channel_id,
-            http,
+            notifier,
             http_client: reqwest::Client::new(),
             players: Vec::new(),
+            spectators: Vec::new(),
             status: RoomStatus::Waiting,
             game_state: GameState::new(),
             settings: RoomSettings::default(),
@@ -85,19 +148,177 @@ impl GameRoom {
             wolf_messages: HashMap::new(),
             day_messages: HashMap::new(),
             vote_messages: HashMap::new(),
+            afk_strikes: HashMap::new(),
+            active_players: Vec::new(),
+            afk_exempt: false,
+            open_seats: Vec::new(),
+            full_viewers: Vec::new(),
             receiver,
             sender: sender.clone(),
             phase_timer_cancel: Arc::new(Mutex::new(None)),
+            wolf_timer: None,
+            phase_deadline: None,
+            wolf_deadline: None,
+            paused: None,
+            lobby_message: None,
+            ready_players: Vec::new(),
+            auto_start_timer: None,
+            auto_start_deadline: None,
+            idle_timer: None,
+            room_registry,
+            player_registry,
+            room_store: None,
+            stats_store: None,
+            rating_store: None,
+            history_store: None,
         };
 
-        let handle = RoomHandle { sender };
+        let handle = RoomHandle { sender, guild_id };
 
         (room, handle)
     }
 
+    /// Dựng lại phòng đang chơi từ snapshot đã lưu.
+    fn restore(
+        saved: SavedRoom,
+        notifier: Arc<dyn GameNotifier>,
+        data: &BotData,
+    ) -> Result<(Self, RoomHandle)> {
+        let players = saved
+            .players
+            .into_iter()
+            .map(SavedPlayer::into_player)
+            .collect::<serde_json::Result<Vec<_>>>()?;
+
+        let (mut room, handle) = GameRoom::new(
+            saved.guild_id,
+            saved.host_id,
+            saved.channel_id,
+            data.roles_json.clone(),
+            notifier,
+            data.room_registry.clone(),
+            data.player_registry.clone(),
+        );
+
+        room.players = players;
+        room.spectators = saved.spectators;
+        room.status = RoomStatus::Starting;
+        room.game_state = saved.game_state;
+        room.settings = saved.settings;
+        room.night_messages = saved.night_messages;
+        room.wolf_messages = saved.wolf_messages;
+        room.day_messages = saved.day_messages;
+        room.vote_messages = saved.vote_messages;
+        room.afk_strikes = saved.afk_strikes;
+        room.active_players = saved.active_players;
+        room.open_seats = saved.open_seats;
+        room.full_viewers = saved.full_viewers;
+        for spectator in room.spectators.iter().filter(|s| s.full_view) {
+            if !room.full_viewers.contains(&spectator.user_id) {
+                room.full_viewers.push(spectator.user_id);
+            }
+        }
+        room.phase_deadline = saved.phase_deadline;
+        room.wolf_deadline = saved.wolf_deadline;
+        room.paused = saved.paused;
+        room.lobby_message = saved.lobby_message;
+        room.room_store = Some(data.room_store.clone());
+        room.stats_store = Some(data.stats.clone());
+        room.rating_store = Some(data.ratings.clone());
+        room.history_store = Some(data.history.clone());
+
+        Ok((room, handle))
+    }
+
+    fn to_saved(&self) -> serde_json::Result<SavedRoom> {
+        Ok(SavedRoom {
+            guild_id: self.guild_id,
+            host_id: self.host_id,
+            channel_id: self.channel_id,
+            players: self
+                .players
+                .iter()
+                .map(SavedPlayer::from_player)
+                .collect::<serde_json::Result<Vec<_>>>()?,
+            spectators: self.spectators.clone(),
+            game_state: self.game_state.clone(),
+            settings: self.settings.clone(),
+            night_messages: self.night_messages.clone(),
+            wolf_messages: self.wolf_messages.clone(),
+            day_messages: self.day_messages.clone(),
+            vote_messages: self.vote_messages.clone(),
+            phase_deadline: self.phase_deadline,
+            wolf_deadline: self.wolf_deadline,
+            paused: self.paused.clone(),
+            lobby_message: self.lobby_message,
+            afk_strikes: self.afk_strikes.clone(),
+            active_players: self.active_players.clone(),
+            open_seats: self.open_seats.clone(),
+            full_viewers: self.full_viewers.clone(),
+        })
+    }
+
+    /// Ghi snapshot phòng để có thể tiếp tục ván chơi nếu bot khởi động lại.
+    async fn persist(&self) {
+        let Some(store) = self.room_store.as_ref() else {
+            return;
+        };
+        if self.status != RoomStatus::Starting {
+            return;
+        }
+
+        let result = match self.to_saved() {
+            Ok(saved) => store.save(&saved).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            tracing::error!("Room {}: Lỗi lưu snapshot: {:?}", self.guild_id, e);
+        }
+    }
+
+    /// Đặt lại các bộ đếm giờ sau khi khôi phục từ snapshot.
+    async fn resume(&mut self) {
+        if self.paused.is_some() {
+            let _ = self
+                .notifier
+                .send_channel(
+                    self.channel_id,
+                    CreateMessage::new().content(
+                        "♻️ Bot vừa khởi động lại. Ván chơi vẫn đang tạm dừng, chủ phòng dùng `/masoi-host pause` để tiếp tục.",
+                    ),
+                )
+                .await;
+            return;
+        }
+
+        if self.game_state.phase == Phase::Night {
+            if let Some(deadline) = self.wolf_deadline {
+                self.set_wolf_timer(seconds_until(deadline));
+            }
+        }
+
+        let phase_seconds = self.phase_deadline.map(seconds_until).unwrap_or(1);
+        self.set_phase_timer(phase_seconds).await;
+
+        let _ = self
+            .notifier
+            .send_channel(
+                self.channel_id,
+                CreateMessage::new().content(format!(
+                    "♻️ Bot vừa khởi động lại, ván chơi được tiếp tục! Phase hiện tại còn **{}** giây.",
+                    phase_seconds
+                )),
+            )
+            .await;
+    }
+
     pub async fn run(mut self) {
         tracing::info!("Room {} started", self.guild_id);
 
+        if self.status == RoomStatus::Waiting {
+            self.set_idle_timer();
+        }
+
         while let Some(event) = self.receiver.recv().await {
             if let Err(e) = self.handle_event(event).await {
                 tracing::error!("Error handling event in room {}: {:?}", self.guild_id, e);
@@ -113,6 +334,12 @@ impl GameRoom {
     }
 
     async fn handle_event(&mut self, event: RoomEvent) -> Result<()> {
+        if let Some(user_id) = event.actor() {
+            if !self.active_players.contains(&user_id) {
+                self.active_players.push(user_id);
+            }
+        }
+
         match event {
             RoomEvent::RegisterInteraction {
                 user_id,
@@ -168,13 +395,14 @@ impl GameRoom {
                     return Ok(());
                 }
 
+                let msg = format!("**[🐺 {}]**: {}", sender_name, content);
                 for player in self.players.iter() {
                     if player.is_werewolf() && player.alive && player.user_id != sender_id {
-                        let dm = player.user_id.create_dm_channel(&self.http).await?;
-                        let msg = format!("**[🐺 {}]**: {}", sender_name, content);
-                        let _ = dm.say(&self.http, msg).await;
+                        let _ = self.notifier.say(player.user_id, msg.clone()).await;
                     }
                 }
+                self.notify_spectators(CreateMessage::new().content(msg), true)
+                    .await;
             }
             RoomEvent::DayChat {
                 sender_id,
@@ -183,7 +411,10 @@ impl GameRoom {
                 attachments,
             } => {
                 // tracing::info!("DEBUG: Phase hiện tại là {:?}", self.game_state.phase);
-                if !matches!(self.game_state.phase, Phase::Day | Phase::Voting) {
+                if !matches!(
+                    self.game_state.phase,
+                    Phase::Day | Phase::Voting | Phase::Defense | Phase::Judgment
+                ) {
                     return Ok(());
                 }
 
@@ -206,18 +437,29 @@ impl GameRoom {
                     can_sender_chat
                 );
 
+                let defending = self
+                    .game_state
+                    .accused
+                    .filter(|_| self.game_state.phase == Phase::Defense);
+                if defending.is_some_and(|accused| accused != sender_id) {
+                    let notifier = self.notifier.clone();
+                    tokio::spawn(async move {
+                        let _ = notifier
+                            .say(
+                                sender_user_id,
+                                "🤐 Chỉ người bị xét xử được nói trong lúc bào chữa!",
+                            )
+                            .await;
+                    });
+                    return Ok(());
+                }
+
                 if is_sender_alive && !can_sender_chat {
-                    let http = self.http.clone();
+                    let notifier = self.notifier.clone();
                     tokio::spawn(async move {
-                        if let Ok(dm) = sender_user_id.create_dm_channel(&http).await {
-                            let _ = dm
-                                .send_message(
-                                    &http,
-                                    CreateMessage::new()
-                                        .content("⚠️ Bạn không thể chat trong hôm nay!"),
-                                )
-                                .await;
-                        }
+                        let _ = notifier
+                            .say(sender_user_id, "⚠️ Bạn không thể chat trong hôm nay!")
+                            .await;
                     });
                     return Ok(());
                 }
@@ -259,13 +501,14 @@ impl GameRoom {
                     format!("🗣️ **{}**: {}", sender_name, content)
                 };
 
-                let http = self.http.clone();
+                let notifier = self.notifier.clone();
 
                 tracing::info!(
                     "DEBUG: Bắt đầu vòng lặp gửi tin cho {} người chơi...",
                     self.players.len()
                 );
 
+                let mut recipients = Vec::new();
                 for player in self.players.iter() {
                     if player.user_id == sender_id {
                         continue;
@@ -280,21 +523,23 @@ impl GameRoom {
                     }
 
                     tracing::info!("DEBUG: Đang chuẩn bị gửi cho {}...", player.name);
+                    recipients.push(player.user_id);
+                }
 
-                    let recipient_id = player.user_id;
-                    let http_clone = http.clone();
+                // Chat của người chết có thể lộ vai trò nên chỉ khán giả xem toàn bộ mới thấy.
+                recipients.extend(
+                    self.spectators
+                        .iter()
+                        .filter(|s| is_sender_alive || s.full_view)
+                        .map(|s| s.user_id),
+                );
+
+                for recipient_id in recipients {
+                    let notifier = notifier.clone();
                     let content_clone = formatted_content.clone();
                     let files_ref = shared_files_arc.clone();
 
                     tokio::spawn(async move {
-                        let dm = match recipient_id.create_dm_channel(&http_clone).await {
-                            Ok(c) => c,
-                            Err(e) => {
-                                tracing::error!("Lỗi tạo DM: {:?}", e);
-                                return;
-                            }
-                        };
-
                         let mut msg_builder = CreateMessage::new().content(content_clone);
 
                         for file in files_ref.iter() {
@@ -303,7 +548,7 @@ impl GameRoom {
                             msg_builder = msg_builder.add_file(attachment);
                         }
 
-                        if let Err(e) = dm.send_message(&http_clone, msg_builder).await {
+                        if let Err(e) = notifier.send_dm(recipient_id, msg_builder).await {
                             tracing::error!("Lỗi gửi tin: {:?}", e);
                         }
                     });
@@ -328,7 +573,7 @@ impl GameRoom {
                     return Ok(());
                 }
 
-                if self.players.len() >= 18 {
+                if self.players.len() >= MAX_PLAYERS {
                     let _ = reply.send(JoinResult::RoomFull);
                     return Ok(());
                 }
@@ -338,11 +583,14 @@ impl GameRoom {
                     return Ok(());
                 }
 
+                self.spectators.retain(|s| s.user_id != user_id);
+
                 let role = crate::roles::create_role(crate::utils::role::RoleId::Villager);
                 self.players
                     .push(Player::new(user_id, name, role, avatar_url));
 
                 let _ = reply.send(JoinResult::Success(self.players.len()));
+                self.on_lobby_changed().await?;
             }
             RoomEvent::LeaveRequest { user_id, reply } => {
                 if self.status != RoomStatus::Waiting {
@@ -352,6 +600,8 @@ impl GameRoom {
 
                 if let Some(index) = self.players.iter().position(|p| p.user_id == user_id) {
                     self.players.remove(index);
+                    self.ready_players.retain(|id| *id != user_id);
+                    self.unregister_player(user_id).await;
 
                     if self.players.is_empty() {
                         self.status = RoomStatus::Ended;
@@ -359,10 +609,50 @@ impl GameRoom {
                     } else {
                         let _ = reply.send(LeaveResult::Success(self.players.len()));
                     }
+                    self.on_lobby_changed().await?;
                 } else {
                     let _ = reply.send(LeaveResult::NotJoined);
                 }
             }
+            RoomEvent::SpectateRequest {
+                user_id,
+                full_view,
+                reply,
+            } => {
+                if self.status == RoomStatus::Ended {
+                    let _ = reply.send(SpectateResult::GameEnded);
+                    return Ok(());
+                }
+
+                if self.players.iter().any(|p| p.user_id == user_id) {
+                    let _ = reply.send(SpectateResult::IsPlayer);
+                    return Ok(());
+                }
+
+                if let Some(index) = self.spectators.iter().position(|s| s.user_id == user_id) {
+                    self.spectators.remove(index);
+                    let _ = reply.send(SpectateResult::Left);
+                    self.persist().await;
+                    return Ok(());
+                }
+
+                self.spectators.push(Spectator { user_id, full_view });
+                if full_view && !self.full_viewers.contains(&user_id) {
+                    self.full_viewers.push(user_id);
+                }
+                let _ = reply.send(SpectateResult::Joined);
+
+                if full_view && self.status == RoomStatus::Starting {
+                    let _ = self
+                        .notifier
+                        .send_dm(
+                            user_id,
+                            CreateMessage::new().add_embed(self.spectator_roles_embed()),
+                        )
+                        .await;
+                }
+                self.persist().await;
+            }
             RoomEvent::StatusRequest { reply } => {
                 let snapshot = RoomSnapshot {
                     status: self.status.clone(),
@@ -372,6 +662,51 @@ impl GameRoom {
                 };
                 let _ = reply.send(snapshot);
             }
+            RoomEvent::SetLobbyMessage { message_id } => {
+                self.lobby_message = Some(message_id);
+                self.refresh_lobby().await;
+            }
+            RoomEvent::ToggleReady { user_id, reply } => {
+                if !self.settings.ready_check || self.status != RoomStatus::Waiting {
+                    let _ = reply.send(ReadyResult::Unavailable);
+                    return Ok(());
+                }
+                if !self.players.iter().any(|p| p.user_id == user_id) {
+                    let _ = reply.send(ReadyResult::NotJoined);
+                    return Ok(());
+                }
+
+                if let Some(index) = self.ready_players.iter().position(|id| *id == user_id) {
+                    self.ready_players.remove(index);
+                    let _ = reply.send(ReadyResult::Unready);
+                } else {
+                    self.ready_players.push(user_id);
+                    let _ = reply.send(ReadyResult::Ready);
+                }
+                self.on_lobby_changed().await?;
+            }
+            RoomEvent::AutoStartTimeout => {
+                if self.status != RoomStatus::Waiting || self.auto_start_deadline.is_none() {
+                    return Ok(());
+                }
+                self.auto_start().await?;
+            }
+            RoomEvent::LobbyIdleTimeout => {
+                if self.status != RoomStatus::Waiting {
+                    return Ok(());
+                }
+                let _ = self
+                    .notifier
+                    .send_channel(
+                        self.channel_id,
+                        CreateMessage::new().content(format!(
+                            "⌛ Phòng chờ đã bị đóng vì không có hoạt động trong {} phút.",
+                            LOBBY_IDLE_TIME / 60
+                        )),
+                    )
+                    .await;
+                self.status = RoomStatus::Ended;
+            }
             RoomEvent::StartGame {
                 user_id,
                 custom_roles,
@@ -384,87 +719,341 @@ impl GameRoom {
                     return Ok(());
                 }
 
-                if self.status != RoomStatus::Waiting {
-                    let _ = reply.send(StartGameResult::Error(
-                        "Trò chơi đã bắt đầu hoặc kết thúc.".to_string(),
-                    ));
+                if let Err(e) = self.assign_roles(custom_roles).await {
+                    let _ = reply.send(StartGameResult::Error(e));
+                    return Ok(());
+                }
+
+                let _ = reply.send(StartGameResult::Success);
+                self.begin_game().await?;
+            }
+            RoomEvent::WolfPhaseTimeout => {
+                tracing::info!("Room {}: Wolf Phase Timeout", self.guild_id);
+                if self.game_state.phase != Phase::Night {
                     return Ok(());
                 }
 
-                let roles_map = match custom_roles {
-                    Some(map) => map,
+                GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.wolf_messages)
+                    .await;
+                self.wolf_messages.clear();
+                self.wolf_deadline = None;
+
+                self.notify_witch_of_attack().await?;
+                self.persist().await;
+            }
+            RoomEvent::EndGame => {
+                if self.status == RoomStatus::Starting {
+                    self.announce("🛑 Ván chơi đã bị huỷ bởi chủ phòng.").await;
+                }
+                self.status = RoomStatus::Ended;
+            }
+            RoomEvent::KickPlayer { target, reply } => {
+                let position = self.players.iter().position(|p| p.user_id == target);
+                let result = if self.status != RoomStatus::Waiting {
+                    HostActionResult::Rejected(
+                        "Chỉ có thể mời người chơi ra khi phòng đang chờ.".to_string(),
+                    )
+                } else if target == self.host_id {
+                    HostActionResult::Rejected(
+                        "Không thể mời chủ phòng ra, hãy chuyển quyền chủ phòng trước.".to_string(),
+                    )
+                } else if let Some(index) = position {
+                    let player = self.players.remove(index);
+                    self.ready_players.retain(|id| *id != target);
+                    self.unregister_player(target).await;
+                    HostActionResult::Done(format!(
+                        "👢 **{}** đã bị mời ra khỏi phòng. Còn lại {} người.",
+                        player.name,
+                        self.players.len()
+                    ))
+                } else {
+                    HostActionResult::Rejected("Người này không ở trong phòng.".to_string())
+                };
+                let _ = reply.send(result);
+                self.on_lobby_changed().await?;
+            }
+            RoomEvent::TransferHost { target, reply } => {
+                let result = match self.players.iter().find(|p| p.user_id == target) {
                     None => {
-                        let players = self.players.len() as u32;
-                        let table = crate::utils::role::get_role_table(players).ok_or_else(|| {
-                            anyhow::anyhow!(
-                                "Không có vai trò mặc định cho {} người chơi (hỗ trợ 4-12).",
-                                players
-                            )
-                        });
+                        HostActionResult::Rejected("Người này không ở trong phòng.".to_string())
+                    }
+                    Some(_) if target == self.host_id => {
+                        HostActionResult::Rejected("Người này đã là chủ phòng.".to_string())
+                    }
+                    Some(player) => {
+                        let message = format!("👑 **{}** đã trở thành chủ phòng mới.", player.name);
+                        self.host_id = target;
+                        HostActionResult::Done(message)
+                    }
+                };
+                let _ = reply.send(result);
+                self.refresh_lobby().await;
+                self.persist().await;
+            }
+            RoomEvent::TogglePause { reply } => {
+                if self.status != RoomStatus::Starting {
+                    let _ = reply.send(HostActionResult::Rejected(
+                        "Chỉ có thể tạm dừng khi ván đang diễn ra.".to_string(),
+                    ));
+                    return Ok(());
+                }
 
-                        match table {
-                            Ok(table) => {
-                                let mut map: HashMap<u8, u8> = HashMap::new();
-                                for (role, count) in table.iter() {
-                                    if *count == 0 {
-                                        continue;
-                                    }
-                                    let id = *role as u8;
-                                    let cnt_u8: u8 = (*count)
-                                        .try_into()
-                                        .map_err(|_| anyhow::anyhow!("Số lượng role quá lớn"))?;
-                                    map.insert(id, cnt_u8);
-                                }
-                                map
-                            }
-                            Err(e) => {
-                                let _ = reply.send(StartGameResult::Error(e.to_string()));
-                                return Ok(());
-                            }
-                        }
+                let message = if let Some(paused) = self.paused.take() {
+                    if let Some(wolf_time) = paused.wolf_remaining {
+                        self.set_wolf_timer(wolf_time);
                     }
+                    self.set_phase_timer(paused.phase_remaining).await;
+                    format!(
+                        "▶️ Ván chơi tiếp tục! Phase hiện tại còn **{}** giây.",
+                        paused.phase_remaining
+                    )
+                } else {
+                    let wolf_remaining = if self.game_state.phase == Phase::Night {
+                        self.wolf_deadline.map(seconds_until)
+                    } else {
+                        None
+                    };
+                    self.paused = Some(PausedTimers {
+                        phase_remaining: self.phase_deadline.map(seconds_until).unwrap_or(1),
+                        wolf_remaining,
+                    });
+                    self.cancel_timers().await;
+                    "⏸️ Chủ phòng đã tạm dừng ván chơi.".to_string()
                 };
 
-                let total_roles: u8 = roles_map.values().sum();
-                if total_roles as usize != self.players.len() {
-                    let msg = format!(
-                        "Tổng số vai trò ({}) phải bằng số người chơi ({}).",
-                        total_roles,
-                        self.players.len()
-                    );
-                    let _ = reply.send(StartGameResult::Error(msg));
+                self.announce(&message).await;
+                let _ = reply.send(HostActionResult::Done(message));
+                self.persist().await;
+            }
+            RoomEvent::SkipPhase { reply } => {
+                if self.status != RoomStatus::Starting {
+                    let _ = reply.send(HostActionResult::Rejected(
+                        "Chỉ có thể bỏ qua phase khi ván đang diễn ra.".to_string(),
+                    ));
                     return Ok(());
                 }
 
-                if *roles_map.get(&0).unwrap_or(&0) == 0 {
-                    let _ = reply.send(StartGameResult::Error(
-                        "Phải có ít nhất 1 Sói trong game.".to_string(),
+                let phase_name = match self.game_state.phase {
+                    Phase::Night => "ban đêm",
+                    Phase::Day => "thời gian thảo luận",
+                    Phase::Voting => "phần bỏ phiếu",
+                    Phase::Election => "phần bầu Thị Trưởng",
+                    Phase::Defense => "phần bào chữa",
+                    Phase::Judgment => "phần phán quyết",
+                    _ => "phase hiện tại",
+                };
+                let message = format!("⏭️ Chủ phòng đã bỏ qua {}.", phase_name);
+
+                self.paused = None;
+                self.afk_exempt = true;
+                self.cancel_timers().await;
+                if self.game_state.phase == Phase::Night {
+                    GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.wolf_messages)
+                        .await;
+                    self.wolf_messages.clear();
+                    self.wolf_deadline = None;
+                }
+
+                self.announce(&message).await;
+                let _ = reply.send(HostActionResult::Done(message));
+                let result = self.on_phase_timeout().await;
+                self.afk_exempt = false;
+                result?;
+            }
+            RoomEvent::OfferSeat {
+                requester,
+                seat,
+                reply,
+            } => {
+                let seat_player = self
+                    .players
+                    .iter()
+                    .find(|p| p.user_id == seat)
+                    .map(|p| (p.name.clone(), p.alive));
+                let result = if self.status != RoomStatus::Starting {
+                    HostActionResult::Rejected(
+                        "Chỉ có thể nhường ghế khi ván đang diễn ra.".to_string(),
+                    )
+                } else if requester != seat && requester != self.host_id {
+                    HostActionResult::Rejected(
+                        "Chỉ chủ phòng hoặc chính người chơi mới được nhường ghế.".to_string(),
+                    )
+                } else if self.open_seats.contains(&seat) {
+                    HostActionResult::Rejected("Ghế này đang chờ người vào thay.".to_string())
+                } else if let Some((_, false)) = seat_player {
+                    HostActionResult::Rejected(
+                        "Người chơi này đã chết, không cần người vào thay.".to_string(),
+                    )
+                } else if let Some((name, true)) = seat_player {
+                    self.open_seats.push(seat);
+                    HostActionResult::Done(format!(
+                        "🔄 Ghế của **{}** đang cần người thay. Ai chưa tham gia ván có thể bấm nút bên dưới để vào chơi tiếp.",
+                        name
+                    ))
+                } else {
+                    HostActionResult::Rejected("Người này không ở trong phòng.".to_string())
+                };
+                let _ = reply.send(result);
+                self.persist().await;
+            }
+            RoomEvent::ClaimSeat {
+                seat,
+                user_id,
+                name,
+                avatar_url,
+                reply,
+            } => {
+                if self.status != RoomStatus::Starting || !self.open_seats.contains(&seat) {
+                    let _ = reply.send(HostActionResult::Rejected(
+                        "Ghế này không còn trống.".to_string(),
+                    ));
+                    return Ok(());
+                }
+                if self.players.iter().any(|p| p.user_id == user_id) {
+                    let _ = reply.send(HostActionResult::Rejected(
+                        "Bạn đang là người chơi trong ván này.".to_string(),
+                    ));
+                    return Ok(());
+                }
+                if self.full_viewers.contains(&user_id) {
+                    let _ = reply.send(HostActionResult::Rejected(
+                        "Bạn đã xem ván này với toàn bộ thông tin nên không thể vào thay."
+                            .to_string(),
                     ));
                     return Ok(());
                 }
 
-                if let Err(e) = self.assign_roles_and_dm(&roles_map).await {
-                    let _ = reply.send(StartGameResult::Error(e.to_string()));
+                let old_name = self
+                    .players
+                    .iter()
+                    .find(|p| p.user_id == seat)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_default();
+                if let Err(e) = transfer_seat(self, seat, user_id, name.clone(), avatar_url) {
+                    let _ = reply.send(HostActionResult::Rejected(
+                        "Không thể chuyển ghế, vui lòng thử lại.".to_string(),
+                    ));
+                    return Err(e.into());
+                }
+
+                self.open_seats.retain(|id| *id != seat);
+                self.spectators.retain(|s| s.user_id != user_id);
+                self.unregister_player(seat).await;
+                self.player_registry
+                    .write()
+                    .await
+                    .insert(user_id, self.channel_id);
+                if self.host_id == seat {
+                    self.host_id = user_id;
+                }
+
+                let message = format!("✅ **{}** đã vào thay ghế của **{}**.", name, old_name);
+                let _ = reply.send(HostActionResult::Done(message.clone()));
+
+                let _ = self
+                    .notifier
+                    .say(
+                        seat,
+                        "👋 Đã có người vào thay ghế của bạn. Cảm ơn bạn đã tham gia!",
+                    )
+                    .await;
+                if let Some(player) = self.players.iter().find(|p| p.user_id == user_id) {
+                    self.send_role_dm(player).await?;
+                    if player.is_werewolf() {
+                        let _ = self
+                            .notifier
+                            .say(user_id, self.wolf_teammates_message(user_id))
+                            .await;
+                    }
+                }
+                self.announce(&message).await;
+                self.persist().await;
+            }
+            RoomEvent::Accuse { user_id, target } => {
+                if self.game_state.phase != Phase::Day || !self.settings.classic_day {
+                    return Ok(());
+                }
+                let can_accuse = self
+                    .players
+                    .iter()
+                    .any(|p| p.user_id == user_id && p.alive && p.can_vote);
+                let target_alive = self.players.iter().any(|p| p.user_id == target && p.alive);
+                if !can_accuse || !target_alive || user_id == target {
                     return Ok(());
                 }
 
-                let _ = reply.send(StartGameResult::Success);
+                self.game_state.accusations.insert(user_id, target);
+                self.game_state.record(GameEventKind::Accusation {
+                    accuser: user_id,
+                    target,
+                });
 
-                self.status = RoomStatus::Starting;
-                self.game_state.phase = Phase::Night;
-                self.night_phase().await?;
+                let name = |id: UserId| {
+                    self.players
+                        .iter()
+                        .find(|p| p.user_id == id)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_default()
+                };
+                let message = format!("☝️ **{}** tố cáo **{}**.", name(user_id), name(target));
+                self.announce(&message).await;
+                self.persist().await;
             }
-            RoomEvent::WolfPhaseTimeout => {
-                tracing::info!("Room {}: Wolf Phase Timeout", self.guild_id);
-                if self.game_state.phase != Phase::Night {
+            RoomEvent::JudgmentVote { user_id, guilty } => {
+                if self.game_state.phase != Phase::Judgment {
+                    return Ok(());
+                }
+                let accused = self.game_state.accused;
+                let can_vote = |p: &Player| p.alive && p.can_vote && Some(p.user_id) != accused;
+                if !self
+                    .players
+                    .iter()
+                    .any(|p| p.user_id == user_id && can_vote(p))
+                {
                     return Ok(());
                 }
 
-                GameRoom::disable_interaction_in_phase(&self.http, &mut self.wolf_messages).await;
-                self.wolf_messages.clear();
+                self.game_state.judgment_votes.insert(user_id, guilty);
+                self.game_state.record(GameEventKind::JudgmentVote {
+                    voter: user_id,
+                    guilty,
+                });
+
+                let all_voted = self
+                    .players
+                    .iter()
+                    .filter(|p| can_vote(p))
+                    .all(|p| self.game_state.judgment_votes.contains_key(&p.user_id));
+                if all_voted {
+                    let _ = self.sender.send(RoomEvent::VoteComplete);
+                }
+                self.persist().await;
             }
-            RoomEvent::EndGame => {
-                self.status = RoomStatus::Ended;
+            RoomEvent::MayorVote { user_id, target } => {
+                if self.game_state.phase != Phase::Election {
+                    return Ok(());
+                }
+                let voter_alive = self.players.iter().any(|p| p.user_id == user_id && p.alive);
+                let target_alive = self.players.iter().any(|p| p.user_id == target && p.alive);
+                if !voter_alive || !target_alive {
+                    return Ok(());
+                }
+
+                self.game_state.election_votes.insert(user_id, target);
+
+                let all_voted = self
+                    .players
+                    .iter()
+                    .filter(|p| p.alive)
+                    .all(|p| self.game_state.election_votes.contains_key(&p.user_id));
+                if all_voted {
+                    let _ = self.sender.send(RoomEvent::VoteComplete);
+                }
+                self.persist().await;
+            }
+            RoomEvent::MayorSuccessor { user_id, target } => {
+                pass_mayor_title(self, user_id, target).await;
+                self.persist().await;
             }
             RoomEvent::PhaseTimeout => {
                 self.on_phase_timeout().await?;
@@ -485,11 +1074,12 @@ impl GameRoom {
 
                 for player in self.players.iter() {
                     if player.is_werewolf() && player.alive {
-                        let _ = player
-                            .user_id
-                            .create_dm_channel(&self.http)
-                            .await?
-                            .say(&self.http, "⚠️ **Còn 10 giây!** Sói hãy chốt phiếu nhanh!")
+                        let _ = self
+                            .notifier
+                            .say(
+                                player.user_id,
+                                "⚠️ **Còn 10 giây!** Sói hãy chốt phiếu nhanh!",
+                            )
                             .await;
                     }
                 }
@@ -499,16 +1089,17 @@ impl GameRoom {
                     Phase::Night => "trời sẽ sáng",
                     Phase::Day => "để thảo luận",
                     Phase::Voting => "sẽ chốt vote",
+                    Phase::Election => "sẽ chốt bầu Thị Trưởng",
+                    Phase::Defense => "để bào chữa",
+                    Phase::Judgment => "sẽ chốt phán quyết",
                     _ => "tới phase tiêp theo",
                 };
 
                 for player in self.players.iter() {
-                    let _ = player
-                        .user_id
-                        .create_dm_channel(&self.http)
-                        .await?
+                    let _ = self
+                        .notifier
                         .say(
-                            &self.http,
+                            player.user_id,
                             format!("⚠️ Còn **10 giây** nữa {}", phase_promps),
                         )
                         .await;
@@ -526,10 +1117,9 @@ impl GameRoom {
                     };
 
                 if !target_alive {
-                    let _ = user_id
-                        .create_dm_channel(&self.http)
-                        .await?
-                        .say(&self.http, "❌ Không có tác dụng lên người chết.")
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Không có tác dụng lên người chết.")
                         .await;
                     return Ok(());
                 }
@@ -541,23 +1131,22 @@ impl GameRoom {
                         .downcast_mut::<crate::roles::Bodyguard>()
                     {
                         if bodyguard.protected_count == 0 {
-                            let _ = user_id
-                                .create_dm_channel(&self.http)
-                                .await?
-                                .say(&self.http, "❌ Bạn đã hết lượt dùng chức năng.")
+                            let _ = self
+                                .notifier
+                                .say(user_id, "❌ Bạn đã hết lượt dùng chức năng.")
                                 .await;
                             return Ok(());
                         }
 
                         bodyguard.protected_person = Some(target);
+                        self.game_state.record(GameEventKind::Protect {
+                            bodyguard: user_id,
+                            target,
+                        });
 
-                        let _ = user_id
-                            .create_dm_channel(&self.http)
-                            .await?
-                            .say(
-                                &self.http,
-                                format!("🛡️ Bạn đã bảo vệ: **{}**.", target_name),
-                            )
+                        let _ = self
+                            .notifier
+                            .say(user_id, format!("🛡️ Bạn đã bảo vệ: **{}**.", target_name))
                             .await;
                     }
                 }
@@ -574,19 +1163,17 @@ impl GameRoom {
                     };
 
                 if !target_alive {
-                    let _ = user_id
-                        .create_dm_channel(&self.http)
-                        .await?
-                        .say(&self.http, "❌ Không có tác dụng lên người chết.")
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Không có tác dụng lên người chết.")
                         .await;
                     return Ok(());
                 }
 
                 if target == user_id {
-                    let _ = user_id
-                        .create_dm_channel(&self.http)
-                        .await?
-                        .say(&self.http, "❌ Bạn không thể xem phe của chính mình.")
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Bạn không thể xem phe của chính mình.")
                         .await;
                     return Ok(());
                 }
@@ -609,32 +1196,39 @@ impl GameRoom {
                         .downcast_mut::<crate::roles::Seer>()
                     {
                         if seer.view_count <= 0 {
-                            let _ = user_id
-                                .create_dm_channel(&self.http)
-                                .await?
-                                .say(&self.http, "❌ Bạn đã hết lượt dùng chức năng.")
+                            let _ = self
+                                .notifier
+                                .say(user_id, "❌ Bạn đã hết lượt dùng chức năng.")
                                 .await;
                             return Ok(());
                         }
 
                         seer.view_count -= 1;
 
-                        let faction_display = if Some(target) == alpha_masked_target {
-                            "Dân Làng"
+                        let seen_faction = if Some(target) == alpha_masked_target {
+                            Faction::Village
                         } else if target_role_id == RoleId::Lycan {
-                            "Ma Sói"
+                            Faction::Werewolf
                         } else {
                             match target_faction {
-                                Faction::Werewolf => "Ma Sói",
-                                _ => "Dân Làng",
+                                Faction::Werewolf => Faction::Werewolf,
+                                _ => Faction::Village,
                             }
                         };
+                        let faction_display = match seen_faction {
+                            Faction::Werewolf => "Ma Sói",
+                            _ => "Dân Làng",
+                        };
+                        self.game_state.record(GameEventKind::SeerView {
+                            seer: user_id,
+                            target,
+                            result: seen_faction,
+                        });
 
-                        let _ = user_id
-                            .create_dm_channel(&self.http)
-                            .await?
+                        let _ = self
+                            .notifier
                             .say(
-                                &self.http,
+                                user_id,
                                 format!(
                                     "👁️ Phe của **{}** là: **{}**.",
                                     target_name, faction_display
@@ -654,13 +1248,11 @@ impl GameRoom {
                     return Ok(());
                 }
 
-                let http = self.http.clone();
+                let notifier = self.notifier.clone();
                 let send_dm = move |msg: String| {
-                    let http = http.clone();
+                    let notifier = notifier.clone();
                     async move {
-                        if let Ok(dm) = user_id.create_dm_channel(&http).await {
-                            let _ = dm.say(&http, msg).await;
-                        }
+                        let _ = notifier.say(user_id, msg).await;
                     }
                 };
 
@@ -776,11 +1368,9 @@ impl GameRoom {
                     return Ok(());
                 }
 
-                let http = self.http.clone();
+                let notifier = self.notifier.clone();
                 let send_dm = |msg: String| async move {
-                    if let Ok(dm) = user_id.create_dm_channel(&http).await {
-                        let _ = dm.say(&http, msg).await;
-                    }
+                    let _ = notifier.say(user_id, msg).await;
                 };
 
                 let get_target_info = |uid: UserId| {
@@ -852,88 +1442,283 @@ impl GameRoom {
                             )
                         };
 
+                        self.game_state.record(GameEventKind::DetectiveCheck {
+                            detective: user_id,
+                            targets: (target1, target2),
+                            same_faction: is_same_faction,
+                        });
+
                         send_dm(result_text).await;
                     }
                 }
                 return Ok(());
             }
-            _ => {}
-        }
-        Ok(())
-    }
+            RoomEvent::WitchPoison { user_id, target } => {
+                if self.game_state.phase != Phase::Night {
+                    return Ok(());
+                }
 
-    pub async fn disable_interaction_in_phase(
-        http: &Arc<Http>,
-        interaction_messages: &mut HashMap<UserId, Vec<(ChannelId, MessageId)>>,
-    ) {
-        let mut set = JoinSet::new();
-        for (user_id, messages) in interaction_messages.drain() {
-            for (channel_id, message_id) in messages {
-                let http = http.clone();
-                set.spawn(async move {
-                    let msg = match channel_id.message(&http, message_id).await {
-                        Ok(m) => m,
-                        Err(_) => return,
+                let (target_alive, target_name) =
+                    match self.players.iter().find(|p| p.user_id == target) {
+                        Some(p) => (p.alive, p.name.clone()),
+                        None => return Ok(()),
                     };
 
-                    let mut new_rows = Vec::new();
-                    for row in msg.components {
-                        let mut buttons = Vec::new();
-                        let mut select_menu: Option<CreateSelectMenu> = None;
-
-                        for component in row.components {
-                            match component {
-                                ActionRowComponent::Button(b) => {
-                                    use serenity::all::ButtonKind;
-
-                                    if let ButtonKind::NonLink { custom_id, style } = &b.data {
-                                        let mut new_btn = CreateButton::new(custom_id.clone())
-                                            .style(*style)
-                                            .disabled(true);
-
-                                        if let Some(label) = &b.label {
-                                            new_btn = new_btn.label(label.clone());
-                                        }
-                                        if let Some(emoji) = &b.emoji {
-                                            new_btn = new_btn.emoji(emoji.clone());
-                                        }
-
-                                        buttons.push(new_btn);
-                                    }
-                                }
-
-                                ActionRowComponent::SelectMenu(m) => {
-                                    let dummy_option =
-                                        CreateSelectMenuOption::new("Đã hết giờ", "expired")
-                                            .emoji('⌛')
-                                            .default_selection(true);
-
-                                    let new_menu = CreateSelectMenu::new(
-                                        m.custom_id.clone().unwrap_or_default(),
-                                        CreateSelectMenuKind::String {
-                                            options: vec![dummy_option],
-                                        },
-                                    )
-                                    .placeholder("⌛ Đã hết thời gian chọn")
-                                    .disabled(true);
-
-                                    select_menu = Some(new_menu);
-                                }
-
-                                _ => {}
-                            }
-                        }
+                if !target_alive {
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Không có tác dụng lên người chết.")
+                        .await;
+                    return Ok(());
+                }
+
+                if self.game_state.night_count == 1 {
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Bạn không thể đầu độc trong đêm đầu tiên.")
+                        .await;
+                    return Ok(());
+                }
 
-                        if !buttons.is_empty() {
-                            new_rows.push(CreateActionRow::Buttons(buttons));
-                        } else if let Some(menu) = select_menu {
-                            new_rows.push(CreateActionRow::SelectMenu(menu));
+                if let Some(player) = self.players.iter_mut().find(|p| p.user_id == user_id) {
+                    if let Some(witch) = player
+                        .role
+                        .as_any_mut()
+                        .downcast_mut::<crate::roles::Witch>()
+                    {
+                        if witch.poison_count == 0 || witch.poisoned_person.is_some() {
+                            let _ = self
+                                .notifier
+                                .say(user_id, "❌ Bạn đã hết lượt dùng chức năng.")
+                                .await;
+                            return Ok(());
                         }
-                    }
-                    let edit = EditMessage::new().content(msg.content).components(new_rows);
 
-                    if let Err(e) = channel_id.edit_message(http, message_id, edit).await {
-                        tracing::warn!("Lỗi disable user {}: {:?}", user_id, e);
+                        witch.poison_count -= 1;
+                        witch.poisoned_person = Some(target);
+                        self.game_state.record(GameEventKind::Poison {
+                            witch: user_id,
+                            target,
+                        });
+
+                        let _ = self
+                            .notifier
+                            .say(user_id, format!("💊 Bạn đã đầu độc: **{}**.", target_name))
+                            .await;
+                    }
+                }
+            }
+            RoomEvent::WitchHeal { user_id, target } => {
+                if self.game_state.phase != Phase::Night {
+                    return Ok(());
+                }
+
+                let target_name = match self.players.iter().find(|p| p.user_id == target) {
+                    Some(p) => p.name.clone(),
+                    None => return Ok(()),
+                };
+
+                if let Some(player) = self.players.iter_mut().find(|p| p.user_id == user_id) {
+                    if let Some(witch) = player
+                        .role
+                        .as_any_mut()
+                        .downcast_mut::<crate::roles::Witch>()
+                    {
+                        if witch.heal_count == 0 {
+                            let _ = self
+                                .notifier
+                                .say(user_id, "❌ Bạn đã hết lượt dùng chức năng.")
+                                .await;
+                            return Ok(());
+                        }
+
+                        if witch.need_help_person != Some(target) {
+                            let _ = self
+                                .notifier
+                                .say(
+                                    user_id,
+                                    "❌ Bình cứu chỉ dùng được cho người đang bị sói tấn công.",
+                                )
+                                .await;
+                            return Ok(());
+                        }
+
+                        witch.healed_person = Some(target);
+                        self.game_state.record(GameEventKind::Heal {
+                            witch: user_id,
+                            target,
+                        });
+
+                        let _ = self
+                            .notifier
+                            .say(user_id, format!("💫 Bạn đã cứu: **{}**.", target_name))
+                            .await;
+                    }
+                }
+            }
+            RoomEvent::PuppeteerTarget { user_id, target } => {
+                if self.game_state.phase != Phase::Night {
+                    return Ok(());
+                }
+
+                let (target_alive, target_name) =
+                    match self.players.iter().find(|p| p.user_id == target) {
+                        Some(p) => (p.alive, p.name.clone()),
+                        None => return Ok(()),
+                    };
+
+                if !target_alive {
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Không có tác dụng lên người chết.")
+                        .await;
+                    return Ok(());
+                }
+
+                if let Some(player) = self.players.iter_mut().find(|p| p.user_id == user_id) {
+                    if let Some(puppeteer) = player
+                        .role
+                        .as_any_mut()
+                        .downcast_mut::<crate::roles::Puppeteer>()
+                    {
+                        if puppeteer.target_count == 0 {
+                            let _ = self
+                                .notifier
+                                .say(user_id, "❌ Bạn đã hết lượt dùng chức năng.")
+                                .await;
+                            return Ok(());
+                        }
+
+                        puppeteer.target_count -= 1;
+                        puppeteer.target_wolf = Some(target);
+
+                        let _ = self
+                            .notifier
+                            .say(
+                                user_id,
+                                format!("🎭 Sói sẽ phải ăn thịt: **{}**.", target_name),
+                            )
+                            .await;
+                    }
+                }
+            }
+            RoomEvent::StalkerKill { user_id, target } => {
+                if self.game_state.phase != Phase::Night {
+                    return Ok(());
+                }
+
+                let (target_alive, target_name) =
+                    match self.players.iter().find(|p| p.user_id == target) {
+                        Some(p) => (p.alive, p.name.clone()),
+                        None => return Ok(()),
+                    };
+
+                if !target_alive {
+                    let _ = self
+                        .notifier
+                        .say(user_id, "❌ Không có tác dụng lên người chết.")
+                        .await;
+                    return Ok(());
+                }
+
+                if let Some(player) = self.players.iter_mut().find(|p| p.user_id == user_id) {
+                    if let Some(stalker) = player
+                        .role
+                        .as_any_mut()
+                        .downcast_mut::<crate::roles::Stalker>()
+                    {
+                        if stalker.kill_count == 0 {
+                            let _ = self
+                                .notifier
+                                .say(user_id, "❌ Bạn đã hết lượt dùng chức năng.")
+                                .await;
+                            return Ok(());
+                        }
+
+                        stalker.kill_count -= 1;
+                        stalker.killed_person = Some(target);
+
+                        let _ = self
+                            .notifier
+                            .say(
+                                user_id,
+                                format!("🔪 Bạn đã chọn ám sát: **{}**.", target_name),
+                            )
+                            .await;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Báo cho Phù Thuỷ biết ai đang bị sói tấn công sau khi sói chốt phiếu.
+    async fn notify_witch_of_attack(&mut self) -> Result<()> {
+        let target = match crate::game::helper::total_voted_wolves_solve(&self.players) {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+
+        let target_name = match self.players.iter().find(|p| p.user_id == target) {
+            Some(p) => p.name.clone(),
+            None => return Ok(()),
+        };
+
+        let mut witch_to_notify = None;
+        for player in self.players.iter_mut().filter(|p| p.alive) {
+            if let Some(witch) = player
+                .role
+                .as_any_mut()
+                .downcast_mut::<crate::roles::Witch>()
+            {
+                witch.need_help_person = Some(target);
+                if witch.heal_count > 0 && player.can_use_skill {
+                    witch_to_notify = Some(player.user_id);
+                }
+            }
+        }
+
+        let witch_id = match witch_to_notify {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+
+        let (channel_id, message_id) = self
+            .notifier
+            .send_dm(
+                witch_id,
+                CreateMessage::new()
+                    .content(format!(
+                        "🩸 Sói đã chọn tấn công **{}**. Bạn có muốn dùng bình cứu?",
+                        target_name
+                    ))
+                    .components(vec![CreateActionRow::Buttons(vec![CreateButton::new(
+                        format!("heal_target_witch_{}", witch_id),
+                    )
+                    .label("💫 Cứu người")
+                    .style(serenity::all::ButtonStyle::Secondary)])]),
+            )
+            .await?;
+
+        self.night_messages
+            .entry(witch_id)
+            .or_default()
+            .push((channel_id, message_id));
+
+        Ok(())
+    }
+
+    pub async fn disable_interaction_in_phase(
+        notifier: &Arc<dyn GameNotifier>,
+        interaction_messages: &mut HashMap<UserId, Vec<(ChannelId, MessageId)>>,
+    ) {
+        let mut set = JoinSet::new();
+        for (user_id, messages) in interaction_messages.drain() {
+            for (channel_id, message_id) in messages {
+                let notifier = notifier.clone();
+                set.spawn(async move {
+                    if let Err(e) = notifier.disable_components(channel_id, message_id).await {
+                        tracing::warn!("Lỗi disable user {}: {:?}", user_id, e);
                     }
                 });
             }
@@ -946,8 +1731,7 @@ impl GameRoom {
     }
 
     async fn night_phase(&mut self) -> Result<()> {
-        self.game_state.phase = Phase::Night;
-        self.game_state.night_count += 1;
+        self.game_state.reset_to_night();
 
         tracing::info!(
             "Room {}: Night phase {}",
@@ -955,11 +1739,31 @@ impl GameRoom {
             self.game_state.night_count
         );
 
+        self.notify_spectators(
+            CreateMessage::new()
+                .content(format!("# 🌙 Đêm {} bắt đầu.", self.game_state.night_count)),
+            false,
+        )
+        .await;
+
         execute_night_phase(self).await?;
 
+        self.set_wolf_timer(self.settings.wolf_vote_time);
+        self.set_phase_timer(self.settings.night_time).await;
+        self.persist().await;
+
+        Ok(())
+    }
+
+    fn set_wolf_timer(&mut self, wolf_time: u64) {
+        self.wolf_deadline = Some(Utc::now().timestamp() + wolf_time as i64);
+
+        if let Some(handle) = self.wolf_timer.take() {
+            handle.abort();
+        }
+
         let sender_a = self.sender.clone();
-        let wolf_time = self.settings.wolf_vote_time;
-        tokio::spawn(async move {
+        self.wolf_timer = Some(tokio::spawn(async move {
             if wolf_time > 10 {
                 tokio::time::sleep(std::time::Duration::from_secs(wolf_time - 10)).await;
                 let _ = sender_a.send(RoomEvent::WolfPhaseWarning);
@@ -969,25 +1773,202 @@ impl GameRoom {
                 tokio::time::sleep(std::time::Duration::from_secs(wolf_time)).await;
             }
             let _ = sender_a.send(RoomEvent::WolfPhaseTimeout);
-        });
+        }));
+    }
 
-        let sender_b = self.sender.clone();
-        let night_time = self.settings.night_time;
-        tokio::spawn(async move {
-            if night_time > 10 {
-                tokio::time::sleep(std::time::Duration::from_secs(night_time - 10)).await;
-                let _ = sender_b.send(RoomEvent::PhaseWarning);
-                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-            } else {
-                tokio::time::sleep(std::time::Duration::from_secs(night_time)).await;
+    /// Huỷ bộ đếm giờ của phase hiện tại và của Sói.
+    async fn cancel_timers(&mut self) {
+        if let Some(handle) = self.phase_timer_cancel.lock().await.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.wolf_timer.take() {
+            handle.abort();
+        }
+        self.cancel_lobby_timers();
+    }
+
+    /// Kiểm tra đội hình rồi chia vai, trả về lỗi để báo lại cho người bắt đầu.
+    async fn assign_roles(
+        &mut self,
+        custom_roles: Option<HashMap<u8, u8>>,
+    ) -> std::result::Result<(), String> {
+        if self.status != RoomStatus::Waiting {
+            return Err("Trò chơi đã bắt đầu hoặc kết thúc.".to_string());
+        }
+
+        let roles_map = match custom_roles {
+            Some(map) => map,
+            None => {
+                let mut rng = StdRng::from_entropy();
+                generate_role_composition(
+                    self.players.len(),
+                    &self.settings.banned_roles,
+                    &mut rng,
+                )?
             }
+        };
 
-            let _ = sender_b.send(RoomEvent::PhaseTimeout);
-        });
+        let check = validate_composition(&roles_map, self.players.len());
+        if let Some(e) = check.errors.first() {
+            return Err(e.clone());
+        }
+
+        self.assign_roles_and_dm(&roles_map)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Chuyển phòng sang trạng thái đang chơi và bắt đầu đêm đầu tiên.
+    async fn begin_game(&mut self) -> Result<()> {
+        self.cancel_lobby_timers();
+
+        self.notify_spectators(
+            CreateMessage::new().content("🎬 Trò chơi bắt đầu! Vai trò đã được chia."),
+            false,
+        )
+        .await;
+        self.notify_spectators(
+            CreateMessage::new().add_embed(self.spectator_roles_embed()),
+            true,
+        )
+        .await;
+
+        self.status = RoomStatus::Starting;
+        self.refresh_lobby().await;
+        self.game_state.phase = Phase::Night;
+        self.night_phase().await
+    }
 
+    /// Tự bắt đầu với vai trò mặc định khi mọi người đã sẵn sàng hoặc hết giờ đếm ngược.
+    async fn auto_start(&mut self) -> Result<()> {
+        self.cancel_lobby_timers();
+
+        let result = self.assign_roles(None).await;
+        let content = match &result {
+            Ok(()) => "🎬 Trò chơi tự động bắt đầu! Vai trò đã được chia.".to_string(),
+            Err(e) => format!("❌ Không thể tự bắt đầu trò chơi: {}", e),
+        };
+        let _ = self
+            .notifier
+            .send_channel(self.channel_id, CreateMessage::new().content(content))
+            .await;
+
+        if result.is_ok() {
+            self.begin_game().await?;
+        } else {
+            self.set_idle_timer();
+            self.refresh_lobby().await;
+        }
+        Ok(())
+    }
+
+    /// Gọi sau mỗi thay đổi trong phòng chờ: hẹn lại giờ đóng phòng, cập nhật đếm ngược
+    /// và tự bắt đầu nếu mọi người đã sẵn sàng.
+    async fn on_lobby_changed(&mut self) -> Result<()> {
+        if self.status != RoomStatus::Waiting {
+            return Ok(());
+        }
+
+        self.set_idle_timer();
+
+        let enough_players = self.players.len() >= MIN_PLAYERS;
+        if self.settings.ready_check
+            && enough_players
+            && self
+                .players
+                .iter()
+                .all(|p| self.ready_players.contains(&p.user_id))
+        {
+            return self.auto_start().await;
+        }
+
+        if !enough_players {
+            if let Some(handle) = self.auto_start_timer.take() {
+                handle.abort();
+            }
+            self.auto_start_deadline = None;
+        } else if self.settings.auto_start_time > 0 && self.auto_start_timer.is_none() {
+            self.set_auto_start_timer(self.settings.auto_start_time);
+        }
+
+        self.refresh_lobby().await;
         Ok(())
     }
 
+    /// Hẹn giờ đóng phòng chờ, huỷ hẹn giờ cũ nếu có.
+    fn set_idle_timer(&mut self) {
+        if let Some(handle) = self.idle_timer.take() {
+            handle.abort();
+        }
+
+        let sender = self.sender.clone();
+        self.idle_timer = Some(tokio::spawn(async move {
+            sleep(Duration::from_secs(LOBBY_IDLE_TIME)).await;
+            let _ = sender.send(RoomEvent::LobbyIdleTimeout);
+        }));
+    }
+
+    fn set_auto_start_timer(&mut self, seconds: u64) {
+        self.auto_start_deadline = Some(Utc::now().timestamp() + seconds as i64);
+
+        let sender = self.sender.clone();
+        self.auto_start_timer = Some(tokio::spawn(async move {
+            sleep(Duration::from_secs(seconds)).await;
+            let _ = sender.send(RoomEvent::AutoStartTimeout);
+        }));
+    }
+
+    fn cancel_lobby_timers(&mut self) {
+        if let Some(handle) = self.idle_timer.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.auto_start_timer.take() {
+            handle.abort();
+        }
+        self.auto_start_deadline = None;
+    }
+
+    fn lobby_view(&self) -> LobbyView<'_> {
+        LobbyView {
+            host_id: self.host_id,
+            players: &self.players,
+            status: &self.status,
+            ready: self
+                .settings
+                .ready_check
+                .then_some(self.ready_players.as_slice()),
+            auto_start_at: self.auto_start_deadline,
+        }
+    }
+
+    /// Cập nhật embed phòng chờ theo danh sách người chơi và trạng thái hiện tại.
+    async fn refresh_lobby(&self) {
+        let Some(message_id) = self.lobby_message else {
+            return;
+        };
+
+        let view = self.lobby_view();
+        let edit = EditMessage::new()
+            .embed(lobby_embed(&view))
+            .components(lobby_components(&view));
+        if let Err(e) = self
+            .notifier
+            .edit_message(self.channel_id, message_id, edit)
+            .await
+        {
+            tracing::error!("Room {}: Lỗi cập nhật phòng chờ: {:?}", self.guild_id, e);
+        }
+    }
+
+    /// Thông báo thao tác của chủ phòng tới mọi người chơi và khán giả.
+    pub(crate) async fn announce(&self, message: &str) {
+        for player in self.players.iter() {
+            let _ = self.notifier.say(player.user_id, message.to_string()).await;
+        }
+        self.notify_spectators(CreateMessage::new().content(message), false)
+            .await;
+    }
+
     async fn solve_phase(&mut self) -> Result<()> {
         tracing::info!("Room {}: Solve phase", self.guild_id);
         println!("Room {}: Solve phase", self.guild_id);
@@ -998,7 +1979,7 @@ impl GameRoom {
     }
 
     async fn day_phase(&mut self) -> Result<()> {
-        self.game_state.phase = Phase::Day;
+        self.game_state.set_phase(Phase::Day);
 
         tracing::info!("Room {}: Day phase", self.guild_id);
 
@@ -1006,62 +1987,124 @@ impl GameRoom {
 
         execute_day_phase(self).await?;
 
-        let sender = self.sender.clone();
-        let discuss_time = self.settings.discuss_time;
-        tokio::spawn(async move {
-            if discuss_time > 10 {
-                tokio::time::sleep(std::time::Duration::from_secs(discuss_time - 10)).await;
-                let _ = sender.send(RoomEvent::PhaseWarning);
-                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-            } else {
-                tokio::time::sleep(std::time::Duration::from_secs(discuss_time)).await;
-            }
-            let _ = sender.send(RoomEvent::PhaseTimeout);
-        });
+        self.set_phase_timer(self.settings.discuss_time).await;
+        self.persist().await;
+
+        Ok(())
+    }
+
+    /// Bầu Thị Trưởng, dùng chung thời gian với phần bỏ phiếu treo cổ.
+    async fn election_phase(&mut self) -> Result<()> {
+        self.game_state.set_phase(Phase::Election);
+
+        tracing::info!("Room {}: Election phase", self.guild_id);
+
+        execute_election_phase(self).await?;
+
+        self.set_phase_timer(self.settings.vote_time).await;
+        self.persist().await;
 
         Ok(())
     }
 
     async fn vote_phase(&mut self) -> Result<()> {
-        self.game_state.phase = Phase::Voting;
+        self.game_state.set_phase(Phase::Voting);
 
         tracing::info!("Room {}: Vote phase", self.guild_id);
 
         println!("Room {}: Vote phase", self.guild_id);
 
+        self.notify_spectators(
+            CreateMessage::new().content("# 🗳️ Bắt đầu bỏ phiếu treo cổ."),
+            false,
+        )
+        .await;
+
         execute_vote_phase(self).await?;
 
-        let sender = self.sender.clone();
-        let vote_time = self.settings.vote_time;
-        tokio::spawn(async move {
-            if vote_time > 10 {
-                tokio::time::sleep(std::time::Duration::from_secs(vote_time - 10)).await;
-                let _ = sender.send(RoomEvent::PhaseWarning);
-                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-            } else {
-                tokio::time::sleep(std::time::Duration::from_secs(vote_time)).await;
-            }
-            let _ = sender.send(RoomEvent::PhaseTimeout);
-        });
+        self.set_phase_timer(self.settings.vote_time).await;
+        self.persist().await;
 
         Ok(())
     }
 
     async fn check_end_game(&mut self) -> Result<bool> {
-        if let Some(victory) = crate::game::helper::check_victory(&self.players) {
+        if let Some(victory) = execute_check_end_game(self).await? {
             tracing::info!(
                 "Room {}: Game ended, winner: {:?}",
                 self.guild_id,
                 victory.winner
             );
 
+            self.game_state.record(GameEventKind::GameEnded {
+                winner: victory.faction,
+            });
+            self.game_state.set_phase(Phase::Ended);
             self.status = RoomStatus::Ended;
+
+            if let Some(stats) = self.stats_store.as_ref() {
+                let records = build_game_records(self, &victory);
+                if let Err(e) = stats.record_game(&records).await {
+                    tracing::error!("Room {}: Lỗi lưu thống kê: {:?}", self.guild_id, e);
+                }
+            }
+            if let Some(history) = self.history_store.as_ref() {
+                let record = build_game_record(self, &victory);
+                if let Err(e) = history.save(&record).await {
+                    tracing::error!("Room {}: Lỗi lưu biên bản ván chơi: {:?}", self.guild_id, e);
+                }
+            }
+            self.update_ratings(&victory).await;
             return Ok(true);
         }
         Ok(false)
     }
 
-    async fn set_phase_timer(&self, seconds: u64) {
+    /// Rating hiện tại của người chơi trong phòng, `None` nếu không có kết nối database.
+    async fn player_ratings(&self) -> Option<HashMap<UserId, f64>> {
+        let store = self.rating_store.as_ref()?;
+        let ids: Vec<UserId> = self.players.iter().map(|p| p.user_id).collect();
+
+        match store.get_many(self.guild_id, &ids).await {
+            Ok(ratings) => Some(ratings),
+            Err(e) => {
+                tracing::error!("Room {}: Lỗi tải rating: {:?}", self.guild_id, e);
+                None
+            }
+        }
+    }
+
+    async fn update_ratings(&self, victory: &VictoryResult) {
+        let (Some(store), Some(ratings)) =
+            (self.rating_store.as_ref(), self.player_ratings().await)
+        else {
+            return;
+        };
+
+        let rated: Vec<RatedPlayer> = self
+            .players
+            .iter()
+            .map(|p| RatedPlayer {
+                user_id: p.user_id,
+                faction: p.role.faction(),
+                won: player_won(p, victory),
+            })
+            .collect();
+        let balance: i32 = self
+            .players
+            .iter()
+            .map(|p| original_role_id(p).weight())
+            .sum();
+
+        let updated = compute_ratings(&rated, &ratings, balance);
+        if let Err(e) = store.save_many(self.guild_id, &updated).await {
+            tracing::error!("Room {}: Lỗi lưu rating: {:?}", self.guild_id, e);
+        }
+    }
+
+    async fn set_phase_timer(&mut self, seconds: u64) {
+        self.phase_deadline = Some(Utc::now().timestamp() + seconds as i64);
+
         let sender = self.sender.clone();
         let mut cancel = self.phase_timer_cancel.lock().await;
 
@@ -1070,7 +2113,13 @@ impl GameRoom {
         }
 
         let handle = tokio::spawn(async move {
-            sleep(Duration::from_secs(seconds)).await;
+            if seconds > 10 {
+                sleep(Duration::from_secs(seconds - 10)).await;
+                let _ = sender.send(RoomEvent::PhaseWarning);
+                sleep(Duration::from_secs(10)).await;
+            } else {
+                sleep(Duration::from_secs(seconds)).await;
+            }
             let _ = sender.send(RoomEvent::PhaseTimeout);
         });
 
@@ -1079,23 +2128,49 @@ impl GameRoom {
 
     async fn on_phase_timeout(&mut self) -> Result<()> {
         tracing::info!("Room {}: Phase timeout", self.guild_id);
-        if self.status != RoomStatus::Starting {
+        if self.status != RoomStatus::Starting || self.paused.is_some() {
             return Ok(());
         }
+        expire_mayor_succession(self).await;
 
         match self.game_state.phase {
             Phase::Night => {
-                GameRoom::disable_interaction_in_phase(&self.http, &mut self.night_messages).await;
+                GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.night_messages)
+                    .await;
+                process_afk(self, requires_night_action).await;
                 self.solve_phase().await?;
-                self.day_phase().await?;
+                if self.check_end_game().await? {
+                    return Ok(());
+                }
+                check_mayor_succession(self).await;
+
+                let first_day = self.game_state.night_count == 1;
+                if self.settings.mayor_election && first_day && self.game_state.mayor.is_none() {
+                    self.election_phase().await?;
+                } else {
+                    self.day_phase().await?;
+                }
+            }
+            Phase::Election => {
+                self.end_election_phase().await?;
             }
             Phase::Day => {
-                GameRoom::disable_interaction_in_phase(&self.http, &mut self.day_messages).await;
-                self.vote_phase().await?;
+                GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.day_messages)
+                    .await;
+                if self.settings.classic_day {
+                    self.trial_phase().await?;
+                } else {
+                    self.vote_phase().await?;
+                }
+            }
+            Phase::Defense => {
+                self.judgment_phase().await?;
+            }
+            Phase::Judgment => {
+                self.end_judgment_phase().await?;
             }
             Phase::Voting => {
-                GameRoom::disable_interaction_in_phase(&self.http, &mut self.vote_messages).await;
-                self.night_phase().await?;
+                self.end_vote_phase().await?;
             }
             _ => {}
         }
@@ -1105,16 +2180,95 @@ impl GameRoom {
     async fn on_vote_complete(&mut self) -> Result<()> {
         tracing::info!("Room {}: Vote complete (early)", self.guild_id);
 
-        let mut cancel = self.phase_timer_cancel.lock().await;
-        if let Some(handle) = cancel.take() {
-            handle.abort();
+        let phase = self.game_state.phase.clone();
+        if self.status != RoomStatus::Starting
+            || !matches!(phase, Phase::Voting | Phase::Election | Phase::Judgment)
+            || self.paused.is_some()
+        {
+            return Ok(());
+        }
+
+        {
+            let mut cancel = self.phase_timer_cancel.lock().await;
+            if let Some(handle) = cancel.take() {
+                handle.abort();
+            }
+        }
+        expire_mayor_succession(self).await;
+
+        match phase {
+            Phase::Election => self.end_election_phase().await,
+            Phase::Judgment => self.end_judgment_phase().await,
+            _ => self.end_vote_phase().await,
+        }
+    }
+
+    async fn end_election_phase(&mut self) -> Result<()> {
+        GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.vote_messages).await;
+        resolve_election(self).await?;
+        self.day_phase().await
+    }
+
+    /// Ngày cổ điển: đưa người bị tố cáo nhiều nhất ra xét xử, không có ai thì kết thúc ngày luôn.
+    async fn trial_phase(&mut self) -> Result<()> {
+        self.game_state.accused = pick_accused(self);
+        if self.game_state.accused.is_none() {
+            return self.end_judgment_phase().await;
         }
 
+        self.game_state.set_phase(Phase::Defense);
+        tracing::info!("Room {}: Defense phase", self.guild_id);
+
+        execute_defense_phase(self).await?;
+
+        self.set_phase_timer(DEFENSE_TIME).await;
+        self.persist().await;
+
         Ok(())
     }
 
+    async fn judgment_phase(&mut self) -> Result<()> {
+        self.game_state.set_phase(Phase::Judgment);
+        tracing::info!("Room {}: Judgment phase", self.guild_id);
+
+        execute_judgment_phase(self).await?;
+
+        self.set_phase_timer(JUDGMENT_TIME).await;
+        self.persist().await;
+
+        Ok(())
+    }
+
+    async fn end_judgment_phase(&mut self) -> Result<()> {
+        GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.vote_messages).await;
+
+        // Không có ai bị xét xử thì cũng không ai phải bỏ phiếu.
+        let accused = self.game_state.accused;
+        execute_verdict(self).await?;
+        process_afk(self, |p| {
+            accused.is_some_and(|id| id != p.user_id) && requires_vote(p)
+        })
+        .await;
+        if self.check_end_game().await? {
+            return Ok(());
+        }
+        check_mayor_succession(self).await;
+        self.night_phase().await
+    }
+
+    async fn end_vote_phase(&mut self) -> Result<()> {
+        GameRoom::disable_interaction_in_phase(&self.notifier, &mut self.vote_messages).await;
+        execute_hang_phase(self).await?;
+        process_afk(self, requires_vote).await;
+        if self.check_end_game().await? {
+            return Ok(());
+        }
+        check_mayor_succession(self).await;
+        self.night_phase().await
+    }
+
     async fn handle_wolf_vote(&mut self, user_id: UserId, target: UserId) -> Result<()> {
-        use crate::roles::Werewolf;
+        use crate::roles::{KittenWolf, VoodooWerewolf, Werewolf, Wolffluence};
 
         tracing::info!("Processing wolf vote: {} -> {}", user_id, target);
 
@@ -1151,9 +2305,26 @@ impl GameRoom {
             .find(|p| p.user_id == user_id)
             .unwrap();
 
-        if let Some(werewolf) = player.role.as_mut().as_any_mut().downcast_mut::<Werewolf>() {
-            werewolf.vote_bite = Some(target);
+        let role = player.role.as_mut().as_any_mut();
+        let vote_bite = if let Some(werewolf) = role.downcast_mut::<Werewolf>() {
+            Some(&mut werewolf.vote_bite)
+        } else if let Some(kitten) = role.downcast_mut::<KittenWolf>() {
+            Some(&mut kitten.vote_bite)
+        } else if let Some(voodoo) = role.downcast_mut::<VoodooWerewolf>() {
+            Some(&mut voodoo.vote_bite)
+        } else if let Some(wolffluence) = role.downcast_mut::<Wolffluence>() {
+            Some(&mut wolffluence.vote_bite)
+        } else {
+            None
+        };
+
+        if let Some(vote_bite) = vote_bite {
+            *vote_bite = Some(target);
             tracing::info!("Wolf {} voted to bite {}", user_id, target);
+            self.game_state.record(GameEventKind::WolfVote {
+                voter: user_id,
+                target,
+            });
         } else {
             tracing::warn!("Failed to downcast role to Werewolf for user {}", user_id);
             anyhow::bail!("Lỗi xử lý vai trò");
@@ -1162,23 +2333,175 @@ impl GameRoom {
         Ok(())
     }
 
-    async fn handle_hang_vote(&mut self, _user_id: UserId, _target: String) -> Result<()> {
+    async fn handle_hang_vote(&mut self, user_id: UserId, target: String) -> Result<()> {
+        if self.game_state.phase != Phase::Voting {
+            return Ok(());
+        }
+
+        let can_vote = self
+            .players
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .map(|p| p.alive && p.can_vote)
+            .unwrap_or(false);
+
+        if !can_vote {
+            tracing::warn!("Hang vote from ineligible user: {}", user_id);
+            anyhow::bail!("Người chơi không thể bỏ phiếu");
+        }
+
+        let target_id = if target == "skip" {
+            None
+        } else {
+            Some(
+                target
+                    .parse::<u64>()
+                    .map(UserId::new)
+                    .map_err(|_| anyhow::anyhow!("Mục tiêu không hợp lệ"))?,
+            )
+        };
+
+        let target_name = match target_id {
+            None => None,
+            Some(target_id) => match self
+                .players
+                .iter()
+                .find(|p| p.user_id == target_id && p.alive)
+            {
+                Some(p) => Some(p.name.clone()),
+                None => anyhow::bail!("Mục tiêu không hợp lệ"),
+            },
+        };
+
+        if let Some(player) = self.players.iter_mut().find(|p| p.user_id == user_id) {
+            player.role.set_vote_hanged(Some(target));
+            player.voted = true;
+        }
+        self.game_state.record(GameEventKind::HangVote {
+            voter: user_id,
+            target: target_id,
+        });
+
+        let voter_name = self
+            .players
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_default();
+
+        let announcement = match target_name {
+            Some(name) => format!("🗳️ **{}** đã bỏ phiếu treo cổ **{}**.", voter_name, name),
+            None => format!("🗳️ **{}** đã bỏ qua lượt bỏ phiếu.", voter_name),
+        };
+
+        for player in self.players.iter() {
+            let _ = self
+                .notifier
+                .say(player.user_id, announcement.clone())
+                .await;
+        }
+
+        let all_voted = self
+            .players
+            .iter()
+            .filter(|p| p.alive && p.can_vote)
+            .all(|p| p.voted);
+
+        if all_voted {
+            let _ = self.sender.send(RoomEvent::VoteComplete);
+        }
+
         Ok(())
     }
 
+    /// Gửi tin nhắn cho khán giả; `full_view_only` để chỉ gửi cho người được xem toàn bộ thông tin.
+    pub(crate) async fn notify_spectators(&self, message: CreateMessage, full_view_only: bool) {
+        for spectator in self
+            .spectators
+            .iter()
+            .filter(|s| s.full_view || !full_view_only)
+        {
+            if let Err(e) = self
+                .notifier
+                .send_dm(spectator.user_id, message.clone())
+                .await
+            {
+                tracing::error!(
+                    "Room {}: Lỗi gửi tin cho khán giả {}: {:?}",
+                    self.guild_id,
+                    spectator.user_id,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Vai trò của mọi người chơi, chỉ dành cho khán giả xem toàn bộ.
+    fn spectator_roles_embed(&self) -> CreateEmbed {
+        let roles = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(index, p)| {
+                format!(
+                    "`{:02}` {} **{}** — {}",
+                    index + 1,
+                    if p.alive { "🟢" } else { "💀" },
+                    p.name,
+                    original_role_id(p).name()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        CreateEmbed::new()
+            .color(0x9b59b6)
+            .title("👁️ VAI TRÒ CỦA NGƯỜI CHƠI")
+            .description(roles)
+            .footer(CreateEmbedFooter::new(
+                "🤫 Chỉ khán giả mới thấy thông tin này",
+            ))
+    }
+
+    /// Xoá người chơi khỏi `PlayerRegistry` nếu họ vẫn đang được gắn với phòng này.
+    async fn unregister_player(&self, user_id: UserId) {
+        let mut player_registry = self.player_registry.write().await;
+        if player_registry.get(&user_id) == Some(&self.channel_id) {
+            player_registry.remove(&user_id);
+        }
+    }
+
     async fn cleanup(&mut self) {
         tracing::info!("Room {}: Cleaning up", self.guild_id);
 
-        let mut cancel = self.phase_timer_cancel.lock().await;
-        if let Some(handle) = cancel.take() {
-            handle.abort();
+        self.cancel_timers().await;
+
+        self.status = RoomStatus::Ended;
+        self.refresh_lobby().await;
+
+        {
+            let mut registry = self.room_registry.write().await;
+            if registry
+                .get(&self.channel_id)
+                .is_some_and(|handle| handle.sender.same_channel(&self.sender))
+            {
+                registry.remove(&self.channel_id);
+            }
+        }
+
+        let user_ids: Vec<UserId> = self.players.iter().map(|p| p.user_id).collect();
+        for user_id in user_ids {
+            self.unregister_player(user_id).await;
+        }
+
+        if let Some(store) = self.room_store.as_ref() {
+            if let Err(e) = store.delete(self.channel_id).await {
+                tracing::error!("Room {}: Lỗi xoá snapshot: {:?}", self.guild_id, e);
+            }
         }
     }
 
     async fn assign_roles_and_dm(&mut self, roles_map: &HashMap<u8, u8>) -> Result<()> {
-        use crate::utils::embed::create_werewolf_embed;
-        use crate::utils::role::convert_faction_role;
-
         let mut pool: Vec<u8> = Vec::new();
         for (role_id, count) in roles_map.iter() {
             for _ in 0..*count {
@@ -1194,44 +2517,87 @@ impl GameRoom {
             );
         }
 
+        let mut pool = pool
+            .into_iter()
+            .map(|id| {
+                RoleId::from_u8(id).ok_or_else(|| anyhow::anyhow!("Role ID không hợp lệ: {}", id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
         let mut rng = StdRng::from_entropy();
-        pool.shuffle(&mut rng);
+        let ratings = if self.settings.balanced_shuffle {
+            self.player_ratings().await
+        } else {
+            None
+        };
+        match ratings {
+            Some(ratings) => {
+                let ratings: Vec<f64> = self
+                    .players
+                    .iter()
+                    .map(|p| ratings.get(&p.user_id).copied().unwrap_or(DEFAULT_RATING))
+                    .collect();
+                balanced_shuffle(&mut pool, &ratings, &mut rng);
+            }
+            None => pool.shuffle(&mut rng),
+        }
 
-        for (player, role_id_u8) in self.players.iter_mut().zip(pool.into_iter()) {
-            let role_id = crate::utils::role::RoleId::from_u8(role_id_u8)
-                .ok_or_else(|| anyhow::anyhow!("Role ID không hợp lệ: {}", role_id_u8))?;
+        for (player, role_id) in self.players.iter_mut().zip(pool) {
             player.role = crate::roles::create_role(role_id);
+            self.game_state.record(GameEventKind::RoleAssigned {
+                user_id: player.user_id,
+                role_id,
+            });
         }
 
         tracing::info!("Roles assigned: {:?}", self.players);
 
         for player in self.players.iter() {
-            let role_id_u8 = player.role.id() as u8;
-            let role_key = role_id_u8.to_string();
+            self.send_role_dm(player).await?;
+        }
 
-            let (title, description, file_name) = if let Some(info) = self.roles_json.get(&role_key)
-            {
-                let file_name = format!("{}.png", info.e_name.to_lowercase().replace(' ', "_"));
-                let faction_name = convert_faction_role(info.faction);
-                let desc = format!("{}\n\n**Phe:** {}", info.description, faction_name);
-                (format!("{} ({})", info.title, info.e_name), desc, file_name)
-            } else {
-                let file_name = format!(
-                    "{}.png",
-                    player.role.name().to_lowercase().replace(' ', "_")
-                );
-                (
-                    player.role.name().to_string(),
-                    player.role.description().to_string(),
-                    file_name,
+        for wolf in self.players.iter().filter(|p| p.is_werewolf()) {
+            self.notifier
+                .send_dm(
+                    wolf.user_id,
+                    CreateMessage::new().content(self.wolf_teammates_message(wolf.user_id)),
                 )
-            };
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Gửi DM thông báo vai trò (kèm ảnh minh hoạ) cho người chơi.
+    pub(crate) async fn send_role_dm(&self, player: &Player) -> Result<()> {
+        use crate::utils::embed::create_werewolf_embed;
+        use crate::utils::role::convert_faction_role;
 
-            let data_embed = create_werewolf_embed(&file_name, &title, &description).await?;
+        let role_id_u8 = player.role.id() as u8;
+        let role_key = role_id_u8.to_string();
+
+        let (title, description, file_name) = if let Some(info) = self.roles_json.get(&role_key) {
+            let file_name = format!("{}.png", info.e_name.to_lowercase().replace(' ', "_"));
+            let faction_name = convert_faction_role(info.faction);
+            let desc = format!("{}\n\n**Phe:** {}", info.description, faction_name);
+            (format!("{} ({})", info.title, info.e_name), desc, file_name)
+        } else {
+            let file_name = format!(
+                "{}.png",
+                player.role.name().to_lowercase().replace(' ', "_")
+            );
+            (
+                player.role.name().to_string(),
+                player.role.description().to_string(),
+                file_name,
+            )
+        };
 
-            let dm = player.user_id.create_dm_channel(&self.http).await?;
-            dm.send_message(
-                &self.http,
+        let data_embed = create_werewolf_embed(&file_name, &title, &description).await?;
+
+        self.notifier
+            .send_dm(
+                player.user_id,
                 CreateMessage::new()
                     .content(format!(
                         "🎮 Bạn được phân vai: **{}**. Hãy giữ bí mật!!!",
@@ -1241,50 +2607,101 @@ impl GameRoom {
                     .add_file(data_embed.attachment),
             )
             .await?;
-        }
-
-        let wolves: Vec<&Player> = self.players.iter().filter(|p| p.is_werewolf()).collect();
-        for wolf in wolves.iter() {
-            let teammate_str = wolves
-                .iter()
-                .filter(|p| p.user_id != wolf.user_id)
-                .map(|p| format!("**{}** ({})", p.name, p.role.name()))
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            let dm = wolf.user_id.create_dm_channel(&self.http).await?;
-            dm.send_message(
-                &self.http,
-                CreateMessage::new().content(format!(
-                    "Đồng đội của bạn: {}",
-                    if teammate_str.is_empty() {
-                        "Không có đồng đội.".to_string()
-                    } else {
-                        teammate_str
-                    }
-                )),
-            )
-            .await?;
-        }
 
         Ok(())
     }
+
+    /// Tin nhắn liệt kê đồng đội Sói của `wolf_id`.
+    pub(crate) fn wolf_teammates_message(&self, wolf_id: UserId) -> String {
+        let teammate_str = self
+            .players
+            .iter()
+            .filter(|p| p.is_werewolf() && p.user_id != wolf_id)
+            .map(|p| format!("**{}** ({})", p.name, p.role.name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "Đồng đội của bạn: {}",
+            if teammate_str.is_empty() {
+                "Không có đồng đội.".to_string()
+            } else {
+                teammate_str
+            }
+        )
+    }
+}
+
+/// Số giây còn lại tới `deadline` (unix giây), tối thiểu 1.
+fn seconds_until(deadline: i64) -> u64 {
+    (deadline - Utc::now().timestamp()).max(1) as u64
 }
 
+/// Tạo phòng mới và chạy nó trên một task riêng; `settings` phải được tải sẵn từ trước.
 pub fn spawn_room(
     guild_id: GuildId,
     host_id: UserId,
     channel_id: ChannelId,
+    settings: RoomSettings,
     ctx: &Context,
-    roles_json: RolesData,
+    data: &BotData,
 ) -> RoomHandle {
-    let http = ctx.http.clone();
+    let notifier: Arc<dyn GameNotifier> = Arc::new(DiscordNotifier::new(ctx.http.clone()));
+
+    let (mut room, handle) = GameRoom::new(
+        guild_id,
+        host_id,
+        channel_id,
+        data.roles_json.clone(),
+        notifier,
+        data.room_registry.clone(),
+        data.player_registry.clone(),
+    );
+
+    room.room_store = Some(data.room_store.clone());
+    room.stats_store = Some(data.stats.clone());
+    room.rating_store = Some(data.ratings.clone());
+    room.history_store = Some(data.history.clone());
+    room.settings = settings;
+
+    tokio::spawn(room.run());
 
-    let (room, handle) = GameRoom::new(guild_id, host_id, channel_id, roles_json, http);
+    handle
+}
 
-    tokio::spawn(async move {
-        room.run().await;
-    });
+/// Khôi phục các phòng đang chơi dở từ snapshot khi bot khởi động lại.
+pub async fn resume_rooms(http: Arc<Http>, data: &BotData) -> Result<usize> {
+    let saved_rooms = data.room_store.load_all().await?;
+    let mut resumed = 0;
+
+    for saved in saved_rooms {
+        let channel_id = saved.channel_id;
+        let notifier: Arc<dyn GameNotifier> = Arc::new(DiscordNotifier::new(http.clone()));
+
+        let (mut room, handle) = match GameRoom::restore(saved, notifier, data) {
+            Ok(restored) => restored,
+            Err(e) => {
+                tracing::error!("Không khôi phục được phòng ở kênh {}: {:?}", channel_id, e);
+                let _ = data.room_store.delete(channel_id).await;
+                continue;
+            }
+        };
 
-    handle
+        data.room_registry.write().await.insert(channel_id, handle);
+        {
+            let mut player_registry = data.player_registry.write().await;
+            for player in room.players.iter() {
+                player_registry.insert(player.user_id, channel_id);
+            }
+        }
+
+        tokio::spawn(async move {
+            room.resume().await;
+            room.run().await;
+        });
+
+        resumed += 1;
+    }
+
+    Ok(resumed)
 }
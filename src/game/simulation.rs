@@ -0,0 +1,463 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serenity::model::id::UserId;
+
+use crate::game::helper::{
+    apply_deaths, check_victory, player_won, process_vote, total_voted_wolves_solve, SoloWin,
+    Winner,
+};
+use crate::game::phases::resolve_night;
+use crate::roles::{
+    create_role, AlphaWerewolf, Bodyguard, KittenWolf, Puppeteer, Seer, Stalker, VoodooWerewolf,
+    Werewolf, Witch, Wolffluence,
+};
+use crate::types::{DeathCause, Faction, Player};
+use crate::utils::role::RoleId;
+
+/// Số vòng tối đa của một ván mô phỏng, quá giới hạn thì tính là hoà.
+const MAX_ROUNDS: i32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotStrategy {
+    /// Mọi quyết định đều ngẫu nhiên.
+    Random,
+    /// Sói đi săn theo bầy, Tiên Tri công khai sói tìm được, dân làng vote theo thông tin đó.
+    Heuristic,
+}
+
+/// Kết quả của một ván mô phỏng.
+#[derive(Debug, Clone)]
+pub struct SimulatedGame {
+    pub winner: Option<Winner>,
+    pub winners: Vec<UserId>,
+    pub roles: Vec<(UserId, RoleId)>,
+    pub rounds: i32,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RoleStats {
+    pub appearances: u32,
+    pub wins: u32,
+}
+
+/// Thống kê tổng hợp sau nhiều ván mô phỏng với cùng một đội hình.
+#[derive(Debug, Default, Clone)]
+pub struct SimulationReport {
+    pub games: u32,
+    pub village_wins: u32,
+    pub werewolf_wins: u32,
+    pub solo_wins: u32,
+    pub draws: u32,
+    pub total_rounds: u64,
+    pub roles: BTreeMap<u8, RoleStats>,
+}
+
+impl SimulationReport {
+    pub fn add(&mut self, game: &SimulatedGame) {
+        self.games += 1;
+        self.total_rounds += game.rounds as u64;
+
+        match game.winner {
+            Some(Winner::Village) => self.village_wins += 1,
+            Some(Winner::Werewolf) => self.werewolf_wins += 1,
+            Some(Winner::Solo) => self.solo_wins += 1,
+            None => self.draws += 1,
+        }
+
+        for (user_id, role_id) in game.roles.iter() {
+            let stats = self.roles.entry(*role_id as u8).or_default();
+            stats.appearances += 1;
+            if game.winners.contains(user_id) {
+                stats.wins += 1;
+            }
+        }
+    }
+
+    pub fn rate(&self, count: u32) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        count as f64 * 100.0 / self.games as f64
+    }
+
+    pub fn average_rounds(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.total_rounds as f64 / self.games as f64
+    }
+}
+
+/// Thông tin công khai mà bot dân làng dùng để vote (chỉ dùng với `BotStrategy::Heuristic`).
+#[derive(Debug, Default)]
+struct Knowledge {
+    known_wolves: Vec<UserId>,
+    cleared: Vec<UserId>,
+    revealed_seer: Option<UserId>,
+}
+
+/// Chuyển map `{role_id: count}` (định dạng JSON tuỳ chỉnh) thành danh sách vai trò.
+pub fn composition_from_map(roles_map: &HashMap<u8, u8>) -> Result<Vec<RoleId>, String> {
+    let mut ids: Vec<&u8> = roles_map.keys().collect();
+    ids.sort();
+
+    let mut composition = Vec::new();
+    for id in ids {
+        let role_id = match RoleId::from_u8(*id) {
+            Some(RoleId::Dead) | None => return Err(format!("Role ID không hợp lệ: {}", id)),
+            Some(role_id) => role_id,
+        };
+        for _ in 0..roles_map[id] {
+            composition.push(role_id);
+        }
+    }
+    Ok(composition)
+}
+
+/// Chạy `games` ván với cùng một đội hình và tổng hợp kết quả.
+pub fn run_simulations(
+    composition: &[RoleId],
+    games: u32,
+    strategy: BotStrategy,
+    seed: u64,
+) -> SimulationReport {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut report = SimulationReport::default();
+
+    for _ in 0..games {
+        let game = simulate_game(composition, strategy, &mut rng);
+        report.add(&game);
+    }
+
+    report
+}
+
+/// Mô phỏng một ván hoàn chỉnh bằng đúng các luật của bot (không cần Discord).
+pub fn simulate_game(
+    composition: &[RoleId],
+    strategy: BotStrategy,
+    rng: &mut StdRng,
+) -> SimulatedGame {
+    let mut pool = composition.to_vec();
+    pool.shuffle(rng);
+
+    let mut players: Vec<Player> = pool
+        .iter()
+        .enumerate()
+        .map(|(index, role_id)| {
+            Player::new(
+                UserId::new(index as u64 + 1),
+                format!("Bot {}", index + 1),
+                create_role(*role_id),
+                String::new(),
+            )
+        })
+        .collect();
+
+    let roles: Vec<(UserId, RoleId)> = players.iter().map(|p| (p.user_id, p.role.id())).collect();
+
+    let mut knowledge = Knowledge::default();
+    let mut solo_wins: Vec<SoloWin> = Vec::new();
+    let mut rounds = 0;
+
+    let victory = loop {
+        rounds += 1;
+        if rounds > MAX_ROUNDS {
+            break None;
+        }
+
+        play_night(&mut players, rounds, strategy, &mut knowledge, rng);
+        let outcome = resolve_night(&mut players, rounds);
+        solo_wins.extend(outcome.solo_wins);
+
+        if let Some(victory) = check_victory(&players, &solo_wins) {
+            break Some(victory);
+        }
+
+        play_day_vote(&mut players, strategy, &knowledge, rng);
+        if let Some((hanged_id, _)) = process_vote(&mut players, None) {
+            let (_, wins) = apply_deaths(&mut players, &[(hanged_id, DeathCause::Hanged)], rounds);
+            solo_wins.extend(wins);
+        }
+
+        for player in players.iter_mut() {
+            player.role.set_vote_hanged(None);
+            player.reset_round();
+        }
+
+        if let Some(victory) = check_victory(&players, &solo_wins) {
+            break Some(victory);
+        }
+    };
+
+    let (winner, winners) = match victory {
+        Some(victory) => {
+            let winners = players
+                .iter()
+                .filter(|p| player_won(p, &victory))
+                .map(|p| p.user_id)
+                .collect();
+            (Some(victory.winner), winners)
+        }
+        None => (None, Vec::new()),
+    };
+
+    SimulatedGame {
+        winner,
+        winners,
+        roles,
+        rounds,
+    }
+}
+
+fn play_night(
+    players: &mut [Player],
+    night_count: i32,
+    strategy: BotStrategy,
+    knowledge: &mut Knowledge,
+    rng: &mut StdRng,
+) {
+    let alive: Vec<UserId> = players
+        .iter()
+        .filter(|p| p.alive)
+        .map(|p| p.user_id)
+        .collect();
+    let prey: Vec<UserId> = players
+        .iter()
+        .filter(|p| p.alive && p.role.faction() != Faction::Werewolf)
+        .map(|p| p.user_id)
+        .collect();
+
+    let pack_target = match strategy {
+        BotStrategy::Heuristic => knowledge
+            .revealed_seer
+            .filter(|id| prey.contains(id))
+            .or_else(|| prey.choose(rng).copied()),
+        BotStrategy::Random => None,
+    };
+
+    let alpha_masked = players
+        .iter()
+        .filter(|p| p.alive)
+        .find_map(|p| p.role.as_any().downcast_ref::<AlphaWerewolf>())
+        .and_then(|alpha| alpha.mask_wolf);
+
+    let seers: Vec<UserId> = players
+        .iter()
+        .filter(|p| p.alive)
+        .filter(|p| {
+            p.role
+                .as_any()
+                .downcast_ref::<Seer>()
+                .is_some_and(|s| s.view_count > 0)
+        })
+        .map(|p| p.user_id)
+        .collect();
+
+    for seer_id in seers {
+        let candidates: Vec<(UserId, bool)> = players
+            .iter()
+            .filter(|p| p.alive && p.user_id != seer_id)
+            .filter(|p| !knowledge.cleared.contains(&p.user_id))
+            .filter(|p| !knowledge.known_wolves.contains(&p.user_id))
+            .map(|p| {
+                let is_wolf = Some(p.user_id) != alpha_masked
+                    && (p.role.id() == RoleId::Lycan || p.role.faction() == Faction::Werewolf);
+                (p.user_id, is_wolf)
+            })
+            .collect();
+
+        if let Some((target, is_wolf)) = candidates.choose(rng).copied() {
+            if is_wolf {
+                knowledge.known_wolves.push(target);
+                knowledge.revealed_seer = Some(seer_id);
+            } else {
+                knowledge.cleared.push(target);
+            }
+
+            if let Some(seer) = players
+                .iter_mut()
+                .find(|p| p.user_id == seer_id)
+                .and_then(|p| p.role.as_any_mut().downcast_mut::<Seer>())
+            {
+                seer.view_count -= 1;
+            }
+        }
+    }
+
+    let mut forced_target: Option<UserId> = None;
+
+    for player in players.iter_mut().filter(|p| p.alive) {
+        let owner = player.user_id;
+        let others: Vec<UserId> = alive.iter().copied().filter(|id| *id != owner).collect();
+        let role = player.role.as_any_mut();
+
+        let bite = match strategy {
+            BotStrategy::Heuristic => pack_target,
+            BotStrategy::Random => prey.choose(rng).copied(),
+        };
+
+        if let Some(wolf) = role.downcast_mut::<Werewolf>() {
+            wolf.vote_bite = bite;
+        } else if let Some(kitten) = role.downcast_mut::<KittenWolf>() {
+            kitten.vote_bite = bite;
+        } else if let Some(voodoo) = role.downcast_mut::<VoodooWerewolf>() {
+            voodoo.vote_bite = bite;
+        } else if let Some(wolffluence) = role.downcast_mut::<Wolffluence>() {
+            wolffluence.vote_bite = bite;
+        } else if let Some(bodyguard) = role.downcast_mut::<Bodyguard>() {
+            bodyguard.protected_person = match strategy {
+                BotStrategy::Heuristic => knowledge
+                    .revealed_seer
+                    .filter(|id| *id != owner && alive.contains(id))
+                    .or_else(|| others.choose(rng).copied()),
+                BotStrategy::Random => alive.choose(rng).copied(),
+            };
+        } else if let Some(puppeteer) = role.downcast_mut::<Puppeteer>() {
+            if puppeteer.target_count > 0 && rng.gen_bool(0.25) {
+                let wolves: Vec<UserId> = knowledge
+                    .known_wolves
+                    .iter()
+                    .copied()
+                    .filter(|id| alive.contains(id))
+                    .collect();
+                let target = match strategy {
+                    BotStrategy::Heuristic => wolves.choose(rng).copied(),
+                    BotStrategy::Random => others.choose(rng).copied(),
+                };
+                if target.is_some() {
+                    puppeteer.target_wolf = target;
+                    puppeteer.target_count -= 1;
+                    forced_target = target;
+                }
+            }
+        } else if let Some(stalker) = role.downcast_mut::<Stalker>() {
+            let wants_kill = match strategy {
+                BotStrategy::Heuristic => alive.len() <= 4,
+                BotStrategy::Random => rng.gen_bool(0.2),
+            };
+            if stalker.kill_count > 0 && wants_kill {
+                stalker.killed_person = others.choose(rng).copied();
+                if stalker.killed_person.is_some() {
+                    stalker.kill_count -= 1;
+                }
+            }
+        }
+    }
+
+    let bite_target = forced_target.or_else(|| total_voted_wolves_solve(players));
+    let known_wolves: Vec<UserId> = knowledge
+        .known_wolves
+        .iter()
+        .copied()
+        .filter(|id| alive.contains(id))
+        .collect();
+
+    for player in players.iter_mut().filter(|p| p.alive) {
+        let owner = player.user_id;
+        let Some(witch) = player.role.as_any_mut().downcast_mut::<Witch>() else {
+            continue;
+        };
+
+        witch.need_help_person = bite_target;
+
+        let heal = match strategy {
+            BotStrategy::Heuristic => true,
+            BotStrategy::Random => rng.gen_bool(0.5),
+        };
+        if witch.heal_count > 0 && heal {
+            witch.healed_person = bite_target;
+        }
+
+        if night_count > 1 && witch.poison_count > 0 {
+            let target = match strategy {
+                BotStrategy::Heuristic => known_wolves.first().copied(),
+                BotStrategy::Random if rng.gen_bool(0.2) => alive
+                    .iter()
+                    .copied()
+                    .filter(|id| *id != owner)
+                    .collect::<Vec<_>>()
+                    .choose(rng)
+                    .copied(),
+                BotStrategy::Random => None,
+            };
+            if target.is_some() {
+                witch.poisoned_person = target;
+                witch.poison_count -= 1;
+            }
+        }
+    }
+}
+
+fn play_day_vote(
+    players: &mut [Player],
+    strategy: BotStrategy,
+    knowledge: &Knowledge,
+    rng: &mut StdRng,
+) {
+    let alive: Vec<(UserId, Faction)> = players
+        .iter()
+        .filter(|p| p.alive)
+        .map(|p| (p.user_id, p.role.faction()))
+        .collect();
+
+    for player in players.iter_mut().filter(|p| p.alive && p.can_vote) {
+        let owner = player.user_id;
+        let faction = player.role.faction();
+
+        let others: Vec<UserId> = alive
+            .iter()
+            .filter(|(id, _)| *id != owner)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let target = match strategy {
+            BotStrategy::Random => {
+                if rng.gen_bool(0.1) {
+                    None
+                } else {
+                    others.choose(rng).copied()
+                }
+            }
+            BotStrategy::Heuristic if faction == Faction::Werewolf => {
+                let prey: Vec<UserId> = alive
+                    .iter()
+                    .filter(|(id, f)| *id != owner && *f != Faction::Werewolf)
+                    .map(|(id, _)| *id)
+                    .collect();
+                knowledge
+                    .revealed_seer
+                    .filter(|id| prey.contains(id))
+                    .or_else(|| prey.choose(rng).copied())
+            }
+            BotStrategy::Heuristic => {
+                let suspects: Vec<UserId> = knowledge
+                    .known_wolves
+                    .iter()
+                    .copied()
+                    .filter(|id| others.contains(id))
+                    .collect();
+                if let Some(wolf) = suspects.first() {
+                    Some(*wolf)
+                } else {
+                    let unknown: Vec<UserId> = others
+                        .iter()
+                        .copied()
+                        .filter(|id| !knowledge.cleared.contains(id))
+                        .collect();
+                    unknown.choose(rng).or_else(|| others.choose(rng)).copied()
+                }
+            }
+        };
+
+        let vote = match target {
+            Some(id) => id.get().to_string(),
+            None => "skip".to_string(),
+        };
+        player.role.set_vote_hanged(Some(vote));
+        player.voted = true;
+    }
+}
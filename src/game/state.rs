@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serenity::all::UserId;
+use std::collections::HashMap;
+
+use crate::game::events::{GameEvent, GameEventKind};
+use crate::game::helper::SoloWin;
+use crate::types::DeathRecord;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Phase {
@@ -6,15 +12,40 @@ pub enum Phase {
     Night,
     Day,
     Voting,
+    /// Bầu Thị Trưởng vào đầu ngày đầu tiên.
+    Election,
+    /// Ngày cổ điển: người bị tố cáo nhiều nhất tự bào chữa.
+    Defense,
+    /// Ngày cổ điển: dân làng phán quyết có treo cổ người bị tố cáo hay không.
+    Judgment,
     Ended,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub night_count: i32,
     pub phase: Phase,
-    pub log: Vec<String>,
-    max_log_entries: usize,
+    pub events: Vec<GameEvent>,
+    pub deaths: Vec<DeathRecord>,
+    pub solo_wins: Vec<SoloWin>,
+    /// Thị Trưởng hiện tại: phiếu treo cổ tính gấp đôi và quyết định khi hoà phiếu.
+    #[serde(default)]
+    pub mayor: Option<UserId>,
+    /// Phiếu bầu Thị Trưởng (người bầu → người được bầu).
+    #[serde(default)]
+    pub election_votes: HashMap<UserId, UserId>,
+    /// Thị Trưởng vừa chết và chưa chọn người kế nhiệm.
+    #[serde(default)]
+    pub mayor_heir_pending: Option<UserId>,
+    /// Lời tố cáo trong ngày (người tố cáo → nghi phạm).
+    #[serde(default)]
+    pub accusations: HashMap<UserId, UserId>,
+    /// Người đang bị đưa ra xét xử.
+    #[serde(default)]
+    pub accused: Option<UserId>,
+    /// Phiếu phán quyết (`true` là treo cổ).
+    #[serde(default)]
+    pub judgment_votes: HashMap<UserId, bool>,
 }
 
 impl GameState {
@@ -22,31 +53,62 @@ impl GameState {
         Self {
             night_count: 0,
             phase: Phase::Waiting,
-            log: Vec::new(),
-            max_log_entries: 100,
+            events: Vec::new(),
+            deaths: Vec::new(),
+            solo_wins: Vec::new(),
+            mayor: None,
+            election_votes: HashMap::new(),
+            mayor_heir_pending: None,
+            accusations: HashMap::new(),
+            accused: None,
+            judgment_votes: HashMap::new(),
         }
     }
 
-    pub fn add_log(&mut self, entry: String) {
-        self.log.push(entry);
-        if self.log.len() > self.max_log_entries {
-            self.log = self.log[self.log.len() - self.max_log_entries..].to_vec();
+    /// Ghi một sự kiện vào nhật ký ván chơi (không bao giờ bị cắt bớt).
+    pub fn record(&mut self, kind: GameEventKind) {
+        self.events.push(GameEvent::new(self.night_count, kind));
+    }
+
+    /// Ghi nhận những người vừa chết, đồng thời thêm sự kiện `Death` cho từng người.
+    pub fn record_deaths(&mut self, records: &[DeathRecord]) {
+        for record in records.iter() {
+            self.record(GameEventKind::Death {
+                user_id: record.user_id,
+                cause: record.cause,
+            });
         }
+        self.deaths.extend(records.iter().cloned());
+    }
+
+    /// Chuyển sang phase mới và ghi lại sự kiện.
+    pub fn set_phase(&mut self, phase: Phase) {
+        self.phase = phase.clone();
+        self.record(GameEventKind::PhaseChanged { phase });
     }
 
-    pub fn clear_log(&mut self) {
-        self.log.clear();
+    /// Các sự kiện xảy ra trong một đêm (gồm cả ngày ngay sau đêm đó).
+    pub fn events_in_night(&self, night: i32) -> impl Iterator<Item = &GameEvent> {
+        self.events.iter().filter(move |e| e.night == night)
     }
 
     pub fn reset(&mut self) {
         self.night_count = 0;
         self.phase = Phase::Waiting;
-        self.clear_log();
+        self.events.clear();
+        self.deaths.clear();
+        self.solo_wins.clear();
+        self.mayor = None;
+        self.election_votes.clear();
+        self.mayor_heir_pending = None;
+        self.accusations.clear();
+        self.accused = None;
+        self.judgment_votes.clear();
     }
 
     pub fn reset_to_night(&mut self) {
-        self.phase = Phase::Night;
         self.night_count += 1;
+        self.set_phase(Phase::Night);
     }
 }
 
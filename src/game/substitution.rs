@@ -0,0 +1,177 @@
+use serde_json::Value;
+use serenity::all::UserId;
+
+use crate::game::room::GameRoom;
+use crate::roles::{load_role_state, save_role_state};
+
+/// Chuyển ghế của `old_id` cho người chơi mới: vai trò và trạng thái giữ nguyên, mọi chỗ
+/// trong trạng thái vai trò của cả phòng đang trỏ tới `old_id` được đổi sang `new_id`.
+pub fn transfer_seat(
+    room: &mut GameRoom,
+    old_id: UserId,
+    new_id: UserId,
+    name: String,
+    avatar_url: String,
+) -> serde_json::Result<()> {
+    let (old, new) = (old_id.to_string(), new_id.to_string());
+
+    for player in room.players.iter_mut() {
+        let mut state = save_role_state(player.role.as_ref())?;
+        replace_user_id(&mut state, &old, &new);
+        player.role = load_role_state(player.role.id(), state)?;
+
+        if player.user_id == old_id {
+            player.user_id = new_id;
+            player.name = name.clone();
+            player.avatar_url = avatar_url.clone();
+        }
+    }
+
+    for death in room.game_state.deaths.iter_mut() {
+        if death.user_id == old_id {
+            death.user_id = new_id;
+        }
+    }
+    for win in room.game_state.solo_wins.iter_mut() {
+        if win.user_id == old_id {
+            win.user_id = new_id;
+        }
+    }
+    for seat in [
+        &mut room.game_state.mayor,
+        &mut room.game_state.mayor_heir_pending,
+        &mut room.game_state.accused,
+    ] {
+        if *seat == Some(old_id) {
+            *seat = Some(new_id);
+        }
+    }
+
+    let swap = |id: UserId| if id == old_id { new_id } else { id };
+    for votes in [
+        &mut room.game_state.election_votes,
+        &mut room.game_state.accusations,
+    ] {
+        *votes = std::mem::take(votes)
+            .into_iter()
+            .map(|(voter, target)| (swap(voter), swap(target)))
+            .collect();
+    }
+    if let Some(guilty) = room.game_state.judgment_votes.remove(&old_id) {
+        room.game_state.judgment_votes.insert(new_id, guilty);
+    }
+
+    if let Some(strikes) = room.afk_strikes.remove(&old_id) {
+        room.afk_strikes.insert(new_id, strikes);
+    }
+    for user_id in room.active_players.iter_mut() {
+        if *user_id == old_id {
+            *user_id = new_id;
+        }
+    }
+
+    Ok(())
+}
+
+/// UserId được lưu dạng chuỗi (kể cả khi làm khoá của map), nên chỉ cần đổi các chuỗi trùng khớp.
+fn replace_user_id(value: &mut Value, old: &str, new: &str) {
+    match value {
+        Value::String(s) if s == old => *s = new.to_string(),
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                replace_user_id(item, old, new);
+            }
+        }
+        Value::Object(map) => {
+            if let Some(entry) = map.remove(old) {
+                map.insert(new.to_string(), entry);
+            }
+            for (_, entry) in map.iter_mut() {
+                replace_user_id(entry, old, new);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use serenity::all::{ChannelId, GuildId};
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::game::notifier::RecordingNotifier;
+    use crate::roles::{create_role, Bodyguard, Werewolf};
+    use crate::types::Player;
+    use crate::utils::role::RoleId;
+
+    fn room(roles: &[RoleId]) -> GameRoom {
+        let (mut room, _handle) = GameRoom::new(
+            GuildId::new(10),
+            UserId::new(1),
+            ChannelId::new(100),
+            HashMap::new(),
+            Arc::new(RecordingNotifier::new()),
+            Arc::new(RwLock::new(HashMap::new())),
+            Arc::new(RwLock::new(HashMap::new())),
+        );
+        room.players = roles
+            .iter()
+            .enumerate()
+            .map(|(i, role)| {
+                let id = i as u64 + 1;
+                Player::new(
+                    UserId::new(id),
+                    format!("Người chơi {}", id),
+                    create_role(*role),
+                    String::new(),
+                )
+            })
+            .collect();
+        room
+    }
+
+    #[test]
+    fn seat_keeps_its_role_and_every_reference_follows_it() {
+        let mut room = room(&[RoleId::Werewolf, RoleId::Seer, RoleId::Bodyguard]);
+        let (old_id, new_id) = (UserId::new(2), UserId::new(9));
+
+        let wolf = room.players[0].role.as_any_mut();
+        wolf.downcast_mut::<Werewolf>().unwrap().vote_bite = Some(old_id);
+        let guard = room.players[2].role.as_any_mut();
+        guard.downcast_mut::<Bodyguard>().unwrap().protected_person = Some(old_id);
+        room.afk_strikes.insert(old_id, 1);
+        room.active_players.push(old_id);
+
+        transfer_seat(
+            &mut room,
+            old_id,
+            new_id,
+            "Người thay".to_string(),
+            String::new(),
+        )
+        .unwrap();
+
+        let seat = &room.players[1];
+        assert_eq!(seat.user_id, new_id);
+        assert_eq!(seat.name, "Người thay");
+        assert_eq!(seat.role.id(), RoleId::Seer);
+
+        let wolf = room.players[0].role.as_any();
+        assert_eq!(
+            wolf.downcast_ref::<Werewolf>().unwrap().vote_bite,
+            Some(new_id)
+        );
+        let guard = room.players[2].role.as_any();
+        assert_eq!(
+            guard.downcast_ref::<Bodyguard>().unwrap().protected_person,
+            Some(new_id)
+        );
+        assert_eq!(room.afk_strikes.get(&new_id), Some(&1));
+        assert!(!room.afk_strikes.contains_key(&old_id));
+        assert_eq!(room.active_players, vec![new_id]);
+    }
+}
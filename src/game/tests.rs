@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::all::{ChannelId, GuildId, UserId};
+use tokio::sync::{oneshot, RwLock};
+
+use crate::game::notifier::{NotifyTarget, RecordingNotifier};
+use crate::game::room::{GameRoom, RoomHandle};
+use crate::game::{HostActionResult, JoinResult, RoomEvent, StartGameResult};
+use crate::types::data::RolesData;
+use crate::types::Player;
+use crate::utils::role::RoleId;
+
+fn roles_json() -> RolesData {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("data")
+        .join("data.json");
+    serde_json::from_reader(File::open(path).unwrap()).unwrap()
+}
+
+async fn players(handle: &RoomHandle) -> Vec<Player> {
+    let (reply, rx) = oneshot::channel();
+    handle
+        .sender
+        .send(RoomEvent::GetAllPlayers { reply })
+        .unwrap();
+    rx.await.unwrap()
+}
+
+async fn skip_phase(handle: &RoomHandle) {
+    let (reply, rx) = oneshot::channel();
+    handle.sender.send(RoomEvent::SkipPhase { reply }).unwrap();
+    assert!(matches!(rx.await.unwrap(), HostActionResult::Done(_)));
+}
+
+/// Chơi trọn một ván 1 Sói - 3 Dân qua `RecordingNotifier`: Sói cắn một người, rồi bị treo cổ.
+#[tokio::test]
+async fn village_hangs_the_wolf() {
+    let channel_id = ChannelId::new(100);
+    let host_id = UserId::new(1);
+    let notifier = RecordingNotifier::new();
+
+    let (room, handle) = GameRoom::new(
+        GuildId::new(10),
+        host_id,
+        channel_id,
+        roles_json(),
+        Arc::new(notifier.clone()),
+        Arc::new(RwLock::new(HashMap::new())),
+        Arc::new(RwLock::new(HashMap::new())),
+    );
+    let game = tokio::spawn(room.run());
+
+    for id in 1..=4 {
+        let (reply, rx) = oneshot::channel();
+        handle
+            .sender
+            .send(RoomEvent::JoinRequest {
+                user_id: UserId::new(id),
+                name: format!("Người chơi {}", id),
+                avatar_url: String::new(),
+                channel_id,
+                reply,
+            })
+            .unwrap();
+        assert!(matches!(rx.await.unwrap(), JoinResult::Success(_)));
+    }
+
+    let (reply, rx) = oneshot::channel();
+    handle
+        .sender
+        .send(RoomEvent::StartGame {
+            user_id: host_id,
+            custom_roles: Some(HashMap::from([
+                (RoleId::Werewolf as u8, 1),
+                (RoleId::Villager as u8, 3),
+            ])),
+            reply,
+        })
+        .unwrap();
+    assert!(matches!(rx.await.unwrap(), StartGameResult::Success));
+
+    let all = players(&handle).await;
+    let wolf = all.iter().find(|p| p.is_werewolf()).unwrap().user_id;
+    let villagers: Vec<UserId> = all
+        .iter()
+        .filter(|p| !p.is_werewolf())
+        .map(|p| p.user_id)
+        .collect();
+    let victim = villagers[0];
+
+    // Đêm 1: Sói cắn người đầu tiên.
+    handle
+        .sender
+        .send(RoomEvent::WolfVote {
+            user_id: wolf,
+            target: victim,
+        })
+        .unwrap();
+    skip_phase(&handle).await;
+
+    let all = players(&handle).await;
+    assert!(!all.iter().find(|p| p.user_id == victim).unwrap().alive);
+
+    // Ngày 1: bỏ qua thảo luận, hai người còn lại cùng Sói bỏ phiếu treo cổ Sói.
+    skip_phase(&handle).await;
+    for voter in [villagers[1], villagers[2], wolf] {
+        handle
+            .sender
+            .send(RoomEvent::HangVote {
+                user_id: voter,
+                target: wolf.to_string(),
+            })
+            .unwrap();
+    }
+
+    tokio::time::timeout(Duration::from_secs(30), game)
+        .await
+        .expect("ván chơi phải kết thúc")
+        .unwrap();
+
+    for player in all.iter() {
+        assert!(!notifier.messages_for(player.user_id).is_empty());
+    }
+    let result = notifier
+        .messages()
+        .into_iter()
+        .filter(|m| m.target == NotifyTarget::Channel(channel_id))
+        .find(|m| m.payload.to_string().contains("DÂN LÀNG CHIẾN THẮNG"));
+    assert!(result.is_some(), "kết quả ván phải được gửi vào kênh");
+}
@@ -0,0 +1,9 @@
+pub mod bot;
+mod client;
+mod commands;
+mod constants;
+pub mod db;
+pub mod game;
+mod roles;
+mod types;
+pub mod utils;
@@ -1,16 +1,6 @@
-mod bot;
-mod client;
-mod constants;
-mod db;
-mod game;
-mod roles;
-mod types;
-mod utils;
-
-mod commands;
-
 use anyhow::Result;
 use std::env;
+use werewolf_rust::{bot, db};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -1,12 +1,14 @@
-use crate::types::{Faction, Role};
+use crate::types::{DeathCause, Faction, Role};
 use crate::utils::role::RoleId;
+use serde::{Deserialize, Serialize};
 use serenity::model::id::UserId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dead {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
     pub original_role_id: RoleId,
+    pub death_cause: Option<DeathCause>,
     pub loudmouth_player: Option<UserId>,
     pub loudmouth_revealed: bool,
 }
@@ -21,6 +23,7 @@ impl Dead {
             vote_hanged: None,
             death_night,
             original_role_id,
+            death_cause: None,
             loudmouth_player,
             loudmouth_revealed: false,
         }
@@ -1,10 +1,12 @@
 use crate::{
     impl_basic_role,
-    types::{Faction, Role},
+    types::{DeathCause, DeathRecord, Faction, Player},
     utils::role::RoleId,
 };
+use serde::{Deserialize, Serialize};
+use serenity::model::id::UserId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fool {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
@@ -23,5 +25,15 @@ impl_basic_role!(
     Fool,
     RoleId::Fool,
     Faction::Solo,
-    "Bạn phải lừa dân làng treo cổ bạn. Nếu họ treo cổ bạn, bạn thắng."
+    "Bạn phải lừa dân làng treo cổ bạn. Nếu họ treo cổ bạn, bạn thắng.",
+    {
+        fn check_win(&self, owner: UserId, _players: &[Player], death: &DeathRecord) -> bool {
+            death.user_id == owner && death.cause == DeathCause::Hanged
+        }
+
+        /// Thằng Ngố thắng riêng, ván chơi vẫn tiếp tục với những người còn lại.
+        fn win_ends_game(&self) -> bool {
+            false
+        }
+    }
 );
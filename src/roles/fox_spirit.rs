@@ -1,8 +1,9 @@
 use crate::types::{Faction, Role};
 use crate::utils::role::RoleId;
+use serde::{Deserialize, Serialize};
 use serenity::model::id::UserId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FoxSpirit {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
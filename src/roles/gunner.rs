@@ -1,7 +1,8 @@
 use crate::types::{Faction, Role};
 use crate::utils::role::RoleId;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Gunner {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
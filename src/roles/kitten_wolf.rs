@@ -1,13 +1,13 @@
-use crate::{
-    impl_basic_role,
-    types::{Faction, Role},
-    utils::role::RoleId,
-};
+use crate::types::{Faction, Role};
+use crate::utils::role::RoleId;
+use serde::{Deserialize, Serialize};
+use serenity::model::id::UserId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KittenWolf {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
+    pub vote_bite: Option<UserId>,
 }
 
 impl KittenWolf {
@@ -15,13 +15,52 @@ impl KittenWolf {
         Self {
             vote_hanged: None,
             death_night: -1,
+            vote_bite: None,
         }
     }
 }
 
-impl_basic_role!(
-    KittenWolf,
-    RoleId::KittenWolf,
-    Faction::Werewolf,
-    "Bạn là một ma sói. Khi bạn bị giết, vote sói tiếp theo sẽ biến đổi dân làng thành ma sói."
-);
+impl Role for KittenWolf {
+    fn id(&self) -> RoleId {
+        RoleId::KittenWolf
+    }
+    fn faction(&self) -> Faction {
+        Faction::Werewolf
+    }
+    fn description(&self) -> &'static str {
+        "Bạn là một ma sói. Khi bạn bị giết, vote sói tiếp theo sẽ biến đổi dân làng thành ma sói."
+    }
+
+    fn vote_hanged(&self) -> Option<String> {
+        self.vote_hanged.clone()
+    }
+    fn set_vote_hanged(&mut self, target: Option<String>) {
+        self.vote_hanged = target;
+    }
+
+    fn death_night(&self) -> i32 {
+        self.death_night
+    }
+    fn set_death_night(&mut self, night: i32) {
+        self.death_night = night;
+    }
+
+    fn reset_day(&mut self) {
+        self.vote_hanged = None;
+        self.vote_bite = None;
+    }
+
+    fn reset_restrict(&mut self) {}
+
+    fn clone_box(&self) -> Box<dyn Role> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
@@ -78,11 +78,65 @@ pub fn create_role(role_id: RoleId) -> Box<dyn Role> {
     }
 }
 
-/// Macro để giảm boilerplate cho basic roles
+/// Sinh cặp hàm lưu / khôi phục trạng thái role, mỗi `RoleId` ứng với một struct.
+macro_rules! role_state {
+    ($($id:ident => $role:ty),* $(,)?) => {
+        /// Lưu trạng thái role thành JSON để ghi snapshot phòng chơi.
+        pub fn save_role_state(role: &dyn Role) -> serde_json::Result<serde_json::Value> {
+            match role.id() {
+                $(RoleId::$id => match role.as_any().downcast_ref::<$role>() {
+                    Some(state) => serde_json::to_value(state),
+                    None => Ok(serde_json::Value::Null),
+                },)*
+            }
+        }
+
+        /// Dựng lại role từ trạng thái đã lưu bằng `save_role_state`.
+        pub fn load_role_state(
+            role_id: RoleId,
+            state: serde_json::Value,
+        ) -> serde_json::Result<Box<dyn Role>> {
+            match role_id {
+                $(RoleId::$id => Ok(Box::new(serde_json::from_value::<$role>(state)?)),)*
+            }
+        }
+    };
+}
+
+role_state! {
+    Werewolf => Werewolf,
+    Villager => Villager,
+    Bodyguard => Bodyguard,
+    Cursed => Cursed,
+    Seer => Seer,
+    Detective => Detective,
+    Witch => Witch,
+    Fool => Fool,
+    Medium => Medium,
+    Dead => Dead,
+    Maid => Maid,
+    Lycan => Lycan,
+    WolfSeer => WolfSeer,
+    AlphaWerewolf => AlphaWerewolf,
+    FoxSpirit => FoxSpirit,
+    Elder => Elder,
+    Stalker => Stalker,
+    Gunner => Gunner,
+    KittenWolf => KittenWolf,
+    Puppeteer => Puppeteer,
+    Voodoo => VoodooWerewolf,
+    Wolffluence => Wolffluence,
+    Loudmouth => Loudmouth,
+}
+
+/// Macro để giảm boilerplate cho basic roles.
+/// Có thể truyền thêm một khối `{ ... }` để ghi đè các hàm mặc định của `Role` như `check_win`.
 #[macro_export]
 macro_rules! impl_basic_role {
-    ($role:ident, $id:expr, $faction:expr, $desc:literal) => {
+    ($role:ident, $id:expr, $faction:expr, $desc:literal $(, { $($overrides:tt)* })?) => {
         impl $crate::types::Role for $role {
+            $($($overrides)*)?
+
             fn id(&self) -> $crate::utils::role::RoleId {
                 $id
             }
@@ -1,8 +1,9 @@
-use crate::types::{Faction, Role};
+use crate::types::{DeathRecord, Faction, Player, Role};
 use crate::utils::role::RoleId;
+use serde::{Deserialize, Serialize};
 use serenity::model::id::UserId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stalker {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
@@ -58,6 +59,14 @@ impl Role for Stalker {
 
     fn reset_restrict(&mut self) {}
 
+    fn check_win(&self, owner: UserId, players: &[Player], _death: &DeathRecord) -> bool {
+        players
+            .iter()
+            .filter(|p| p.alive)
+            .all(|p| p.user_id == owner)
+            && players.iter().any(|p| p.user_id == owner && p.alive)
+    }
+
     fn clone_box(&self) -> Box<dyn Role> {
         Box::new(self.clone())
     }
@@ -3,8 +3,9 @@ use crate::{
     types::{Faction, Role},
     utils::role::RoleId,
 };
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WolfSeer {
     pub vote_hanged: Option<String>,
     pub death_night: i32,
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use serenity::model::id::UserId;
+
+/// Nguyên nhân cái chết của người chơi.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeathCause {
+    WolfBite,
+    WitchPoison,
+    StalkerKill,
+    Hanged,
+    /// Không hành động quá nhiều lượt liên tiếp.
+    LeftVillage,
+}
+
+impl DeathCause {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeathCause::WolfBite => "Bị sói cắn",
+            DeathCause::WitchPoison => "Bị Phù Thuỷ đầu độc",
+            DeathCause::StalkerKill => "Bị Stalker ám sát",
+            DeathCause::Hanged => "Bị treo cổ",
+            DeathCause::LeftVillage => "Bỏ làng ra đi",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeathRecord {
+    pub user_id: UserId,
+    pub night: i32,
+    pub cause: DeathCause,
+}
@@ -1,9 +1,11 @@
 pub mod data;
+pub mod death;
 pub mod faction;
 pub mod player;
 pub mod role;
 pub mod types;
 
+pub use death::{DeathCause, DeathRecord};
 pub use faction::Faction;
 pub use player::Player;
 pub use role::Role;
@@ -1,5 +1,4 @@
 use super::role::Role;
-use crate::utils::role::RoleId;
 use serenity::model::id::UserId;
 
 pub struct PlayerInfo {
@@ -52,15 +51,7 @@ impl Player {
     }
 
     pub fn is_werewolf(&self) -> bool {
-        matches!(
-            self.role.id(),
-            RoleId::Werewolf
-                | RoleId::WolfSeer
-                | RoleId::AlphaWerewolf
-                | RoleId::KittenWolf
-                | RoleId::Voodoo
-                | RoleId::Wolffluence
-        )
+        self.role.id().is_werewolf()
     }
 }
 
@@ -1,5 +1,8 @@
+use super::death::DeathRecord;
 use super::faction::Faction;
+use super::player::Player;
 use crate::utils::role::RoleId;
+use serenity::model::id::UserId;
 
 pub trait Role: Send + Sync {
     fn id(&self) -> RoleId;
@@ -18,6 +21,16 @@ pub trait Role: Send + Sync {
     fn reset_day(&mut self);
     fn reset_restrict(&mut self);
 
+    /// Điều kiện thắng riêng của vai trò, được kiểm tra sau mỗi cái chết.
+    fn check_win(&self, _owner: UserId, _players: &[Player], _death: &DeathRecord) -> bool {
+        false
+    }
+
+    /// Chiến thắng riêng của vai trò có kết thúc ván chơi hay không.
+    fn win_ends_game(&self) -> bool {
+        true
+    }
+
     fn clone_box(&self) -> Box<dyn Role>;
 
     fn as_any(&self) -> &dyn std::any::Any;
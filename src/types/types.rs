@@ -1,4 +1,4 @@
-use serenity::all::{ComponentInteraction, GuildId, ModalInteraction, UserId};
+use serenity::all::{ChannelId, ComponentInteraction, GuildId, ModalInteraction, UserId};
 pub enum InteractionWrapper {
     Component(ComponentInteraction),
     Modal(ModalInteraction),
@@ -18,4 +18,11 @@ impl InteractionWrapper {
             Self::Modal(i) => i.guild_id,
         }
     }
+
+    pub fn channel_id(&self) -> ChannelId {
+        match self {
+            Self::Component(i) => i.channel_id,
+            Self::Modal(i) => i.channel_id,
+        }
+    }
 }
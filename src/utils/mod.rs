@@ -4,6 +4,7 @@ pub mod embed;
 pub mod response;
 pub mod role;
 pub mod role_parser;
+pub mod role_validator;
 
 pub fn get_player_mut_role<T: 'static>(room: &mut GameRoom, role_id: RoleId) -> Option<&mut T> {
     room.players
@@ -1,6 +1,12 @@
-use crate::types::Faction;
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use crate::constants::{MAX_PLAYERS, MIN_PLAYERS};
+use crate::types::Faction;
+
 pub fn convert_faction_role(role_id: i8) -> &'static str {
     match role_id {
         0 => Faction::Werewolf.name(),
@@ -68,6 +74,55 @@ impl RoleId {
         }
     }
 
+    /// Trọng số cân bằng: dương có lợi cho dân làng, âm có lợi cho sói.
+    pub fn weight(&self) -> i32 {
+        match self {
+            RoleId::Werewolf => -6,
+            RoleId::Villager => 1,
+            RoleId::Bodyguard => 3,
+            RoleId::Cursed => -3,
+            RoleId::Seer => 7,
+            RoleId::Detective => 4,
+            RoleId::Witch => 4,
+            RoleId::Fool => -2,
+            RoleId::Medium => 3,
+            RoleId::Dead => 0,
+            RoleId::Maid => 2,
+            RoleId::Lycan => -1,
+            RoleId::WolfSeer => -7,
+            RoleId::AlphaWerewolf => -8,
+            RoleId::FoxSpirit => 3,
+            RoleId::Elder => 3,
+            RoleId::Stalker => -3,
+            RoleId::Gunner => 3,
+            RoleId::KittenWolf => -7,
+            RoleId::Puppeteer => 2,
+            RoleId::Voodoo => -7,
+            RoleId::Wolffluence => -8,
+            RoleId::Loudmouth => 2,
+        }
+    }
+
+    pub fn is_werewolf(&self) -> bool {
+        matches!(
+            self,
+            RoleId::Werewolf
+                | RoleId::WolfSeer
+                | RoleId::AlphaWerewolf
+                | RoleId::KittenWolf
+                | RoleId::Voodoo
+                | RoleId::Wolffluence
+        )
+    }
+
+    /// Các vai trò cùng đàn bỏ phiếu cắn mỗi đêm.
+    pub fn bites(&self) -> bool {
+        matches!(
+            self,
+            RoleId::Werewolf | RoleId::KittenWolf | RoleId::Voodoo | RoleId::Wolffluence
+        )
+    }
+
     pub fn from_u8(id: u8) -> Option<Self> {
         match id {
             0 => Some(RoleId::Werewolf),
@@ -98,81 +153,196 @@ impl RoleId {
     }
 }
 
-pub fn get_role_table(players: u32) -> Option<&'static [(RoleId, u32)]> {
-    match players {
-        4 => Some(&[
-            (RoleId::Werewolf, 1),
-            (RoleId::Villager, 2),
-            (RoleId::Bodyguard, 1),
-        ]),
-        5 => Some(&[
-            (RoleId::Werewolf, 1),
-            (RoleId::Villager, 2),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-        ]),
-        6 => Some(&[
-            (RoleId::Werewolf, 2),
-            (RoleId::Villager, 1),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Medium, 1),
-        ]),
-        7 => Some(&[
-            (RoleId::Werewolf, 2),
-            (RoleId::Villager, 1),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Medium, 1),
-            (RoleId::Detective, 1),
-        ]),
-        8 => Some(&[
-            (RoleId::Werewolf, 2),
-            (RoleId::Villager, 1),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Medium, 1),
-            (RoleId::Detective, 1),
-            (RoleId::Cursed, 1),
-        ]),
-        9 => Some(&[
-            (RoleId::Werewolf, 2),
-            (RoleId::Villager, 2),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Medium, 1),
-            (RoleId::Detective, 1),
-            (RoleId::Cursed, 1),
-        ]),
-        10 => Some(&[
-            (RoleId::Werewolf, 3),
-            (RoleId::Villager, 2),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Detective, 1),
-            (RoleId::Cursed, 1),
-            (RoleId::Seer, 1),
-        ]),
-        11 => Some(&[
-            (RoleId::Werewolf, 3),
-            (RoleId::Villager, 2),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Detective, 1),
-            (RoleId::Cursed, 1),
-            (RoleId::Seer, 1),
-            (RoleId::Fool, 1),
-        ]),
-        12 => Some(&[
-            (RoleId::Werewolf, 3),
-            (RoleId::Villager, 3),
-            (RoleId::Bodyguard, 1),
-            (RoleId::Witch, 1),
-            (RoleId::Detective, 1),
-            (RoleId::Cursed, 1),
-            (RoleId::Seer, 1),
-            (RoleId::Fool, 1),
-        ]),
-        _ => None,
+/// Các vai trò chỉ xuất hiện tối đa một lần trong một ván.
+const WOLF_POWER_ROLES: &[RoleId] = &[
+    RoleId::WolfSeer,
+    RoleId::AlphaWerewolf,
+    RoleId::KittenWolf,
+    RoleId::Voodoo,
+    RoleId::Wolffluence,
+];
+
+const VILLAGE_POWER_ROLES: &[RoleId] = &[
+    RoleId::Bodyguard,
+    RoleId::Seer,
+    RoleId::Detective,
+    RoleId::Witch,
+    RoleId::Medium,
+    RoleId::Maid,
+    RoleId::FoxSpirit,
+    RoleId::Elder,
+    RoleId::Gunner,
+    RoleId::Puppeteer,
+    RoleId::Loudmouth,
+];
+
+const VILLAGE_RISK_ROLES: &[RoleId] = &[RoleId::Cursed, RoleId::Lycan];
+
+const SOLO_ROLES: &[RoleId] = &[RoleId::Fool, RoleId::Stalker];
+
+/// Các vai trò có thể bị cấm khi chia vai tự động.
+pub fn bannable_roles() -> impl Iterator<Item = RoleId> {
+    WOLF_POWER_ROLES
+        .iter()
+        .chain(VILLAGE_POWER_ROLES)
+        .chain(VILLAGE_RISK_ROLES)
+        .chain(SOLO_ROLES)
+        .copied()
+}
+
+/// Ma Sói và Dân Làng là nền của mọi đội hình nên không thể bị cấm.
+pub fn validate_banned_roles(banned: &[RoleId]) -> Result<(), String> {
+    match banned
+        .iter()
+        .find(|role| matches!(role, RoleId::Werewolf | RoleId::Villager))
+    {
+        Some(role) => Err(format!(
+            "Không thể cấm {}: mọi đội hình đều cần vai trò này.",
+            role.name()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Tạo đội hình cân bằng (có yếu tố ngẫu nhiên) cho `players` người chơi.
+///
+/// Tổng trọng số của đội hình được kéo về gần 0. Các vai trò trong `banned` không được chọn,
+/// riêng Ma Sói thường luôn có ít nhất 1 nên cấm Ma Sói hoặc Dân Làng sẽ bị từ chối.
+pub fn generate_role_composition(
+    players: usize,
+    banned: &[RoleId],
+    rng: &mut impl Rng,
+) -> Result<HashMap<u8, u8>, String> {
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+        return Err(format!(
+            "Không thể tạo vai trò cho {} người chơi (hỗ trợ {}-{}).",
+            players, MIN_PLAYERS, MAX_PLAYERS
+        ));
+    }
+    validate_banned_roles(banned)?;
+
+    let allowed = |role: &&RoleId| !banned.contains(role);
+    let mut roles: Vec<RoleId> = Vec::with_capacity(players);
+
+    let wolf_count = (players + 2) / 4;
+    let mut wolf_powers: Vec<RoleId> = WOLF_POWER_ROLES.iter().filter(allowed).copied().collect();
+    wolf_powers.shuffle(rng);
+
+    roles.push(RoleId::Werewolf);
+    for _ in 1..wolf_count {
+        let special = if players >= 10 && rng.gen_bool(0.4) {
+            wolf_powers.pop()
+        } else {
+            None
+        };
+        roles.push(special.unwrap_or(RoleId::Werewolf));
+    }
+
+    if players >= 11 && rng.gen_bool(0.5) {
+        if let Some(solo) = SOLO_ROLES
+            .iter()
+            .filter(allowed)
+            .collect::<Vec<_>>()
+            .choose(rng)
+        {
+            roles.push(**solo);
+        }
+    }
+
+    if players >= 8 && rng.gen_bool(0.5) {
+        if let Some(risk) = VILLAGE_RISK_ROLES
+            .iter()
+            .filter(allowed)
+            .collect::<Vec<_>>()
+            .choose(rng)
+        {
+            roles.push(**risk);
+        }
+    }
+
+    let mut powers: Vec<RoleId> = VILLAGE_POWER_ROLES
+        .iter()
+        .filter(allowed)
+        .copied()
+        .collect();
+    powers.shuffle(rng);
+
+    // Phần trọng số còn thiếu nếu lấp toàn bộ chỗ trống bằng Dân Làng (+1 mỗi người).
+    let mut balance: i32 = roles.iter().map(|r| r.weight()).sum();
+    while roles.len() < players {
+        let remaining = (players - roles.len()) as i32;
+        let missing = -(balance + remaining);
+        if missing <= 0 || powers.is_empty() {
+            break;
+        }
+
+        let pick = powers
+            .iter()
+            .position(|r| r.weight() - 1 <= missing + 2)
+            .unwrap_or(0);
+        let role = powers.remove(pick);
+        balance += role.weight();
+        roles.push(role);
+    }
+
+    while roles.len() < players {
+        roles.push(RoleId::Villager);
+    }
+
+    let mut map: HashMap<u8, u8> = HashMap::new();
+    for role in roles {
+        *map.entry(role as u8).or_insert(0) += 1;
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn composition_fits_every_supported_player_count() {
+        let banned = [RoleId::Seer, RoleId::Fool];
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for players in MIN_PLAYERS..=MAX_PLAYERS {
+                let roles = generate_role_composition(players, &banned, &mut rng).unwrap();
+
+                let count = |filter: &dyn Fn(RoleId) -> bool| -> usize {
+                    roles
+                        .iter()
+                        .filter(|(id, _)| filter(RoleId::from_u8(**id).unwrap()))
+                        .map(|(_, count)| *count as usize)
+                        .sum()
+                };
+                let wolves = count(&|role| role.is_werewolf());
+
+                assert_eq!(count(&|_| true), players);
+                assert!(roles.contains_key(&(RoleId::Werewolf as u8)));
+                assert!(wolves * 2 < players, "{} sói / {} người", wolves, players);
+                assert!(banned
+                    .iter()
+                    .all(|role| !roles.contains_key(&(*role as u8))));
+            }
+        }
+    }
+
+    #[test]
+    fn composition_rejects_unsupported_player_counts() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(generate_role_composition(MIN_PLAYERS - 1, &[], &mut rng).is_err());
+        assert!(generate_role_composition(MAX_PLAYERS + 1, &[], &mut rng).is_err());
+    }
+
+    #[test]
+    fn werewolf_and_villager_cannot_be_banned() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for role in [RoleId::Werewolf, RoleId::Villager] {
+            assert!(validate_banned_roles(&[RoleId::Seer, role]).is_err());
+            assert!(generate_role_composition(8, &[role], &mut rng).is_err());
+        }
+        assert!(validate_banned_roles(&bannable_roles().collect::<Vec<_>>()).is_ok());
     }
 }
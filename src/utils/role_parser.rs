@@ -1,6 +1,8 @@
 use serde_json::Value;
 use std::collections::HashMap;
 
+use crate::utils::role_validator::validate_role_ids;
+
 pub fn get_role_id_by_name(name: &str) -> Option<u8> {
     let key = name.trim().to_lowercase();
     match key.as_str() {
@@ -81,6 +83,8 @@ pub fn parse_roles_from_string(input: &str) -> Result<HashMap<u8, u8>, String> {
 
         *roles.entry(role_id).or_insert(0) += count;
     }
+
+    validate_role_ids(&roles)?;
     Ok(roles)
 }
 
@@ -127,5 +131,6 @@ pub fn parse_roles_from_json_string(input: &str) -> Result<HashMap<u8, u8>, Stri
         roles.insert(role_id, count);
     }
 
+    validate_role_ids(&roles)?;
     Ok(roles)
 }
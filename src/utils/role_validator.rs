@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use crate::roles::create_role;
+use crate::types::Faction;
+use crate::utils::role::RoleId;
+
+/// Vai trò cung cấp thông tin cho dân làng.
+const INFORMATION_ROLES: &[RoleId] = &[RoleId::Seer, RoleId::Detective, RoleId::FoxSpirit];
+
+/// Độ lệch điểm cân bằng tối đa trước khi cảnh báo.
+const BALANCE_TOLERANCE: i32 = 6;
+
+/// Kết quả kiểm tra đội hình: lỗi chặn việc bắt đầu, cảnh báo chỉ để host cân nhắc.
+#[derive(Debug, Default, Clone)]
+pub struct CompositionCheck {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl CompositionCheck {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Từ chối các role id không tồn tại hoặc không thể phân vai (Người Chết).
+pub fn validate_role_ids(roles: &HashMap<u8, u8>) -> Result<(), String> {
+    let mut ids: Vec<&u8> = roles.keys().collect();
+    ids.sort();
+
+    for id in ids {
+        match RoleId::from_u8(*id) {
+            None => return Err(format!("Role ID {} không tồn tại (hợp lệ: 0-22).", id)),
+            Some(RoleId::Dead) => {
+                return Err("Không thể phân vai Người Chết (ID 9).".to_string());
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Kiểm tra đội hình cho `players` người chơi.
+pub fn validate_composition(roles: &HashMap<u8, u8>, players: usize) -> CompositionCheck {
+    let mut check = CompositionCheck::default();
+
+    if let Err(e) = validate_role_ids(roles) {
+        check.errors.push(e);
+        return check;
+    }
+
+    let total: usize = roles.values().map(|c| *c as usize).sum();
+    if total != players {
+        check.errors.push(format!(
+            "Tổng số vai trò ({}) phải bằng số người chơi ({}).",
+            total, players
+        ));
+        return check;
+    }
+
+    let mut ids: Vec<(RoleId, usize)> = roles
+        .iter()
+        .filter_map(|(id, count)| RoleId::from_u8(*id).map(|r| (r, *count as usize)))
+        .collect();
+    ids.sort_by_key(|(role, _)| *role as u8);
+
+    let count_faction = |faction: Faction| -> usize {
+        ids.iter()
+            .filter(|(role, _)| create_role(*role).faction() == faction)
+            .map(|(_, count)| count)
+            .sum()
+    };
+
+    let wolves = count_faction(Faction::Werewolf);
+    if wolves == 0 {
+        check
+            .errors
+            .push("Phải có ít nhất 1 Sói trong game.".to_string());
+        return check;
+    }
+
+    let others = players - wolves;
+    if wolves >= others {
+        check.errors.push(format!(
+            "Số Sói ({}) đã bằng hoặc nhiều hơn số người còn lại ({}), phe Sói sẽ thắng ngay từ đêm đầu.",
+            wolves, others
+        ));
+        return check;
+    }
+
+    for (role, count) in ids.iter() {
+        if *count > 1 && !matches!(role, RoleId::Werewolf | RoleId::Villager) {
+            check.warnings.push(format!(
+                "Có {} **{}** trong ván, vai trò này thường chỉ nên có 1.",
+                count,
+                role.name()
+            ));
+        }
+    }
+
+    if !ids.iter().any(|(role, _)| INFORMATION_ROLES.contains(role)) {
+        check.warnings.push(
+            "Không có vai trò thu thập thông tin (Tiên Tri, Thám Tử, Cáo), dân làng sẽ rất khó tìm ra Sói."
+                .to_string(),
+        );
+    }
+
+    if count_faction(Faction::Solo) > 1 {
+        check
+            .warnings
+            .push("Có nhiều hơn 1 vai trò phe Solo trong ván.".to_string());
+    }
+
+    let balance: i32 = ids
+        .iter()
+        .map(|(role, count)| role.weight() * *count as i32)
+        .sum();
+    if balance > BALANCE_TOLERANCE {
+        check.warnings.push(format!(
+            "Đội hình nghiêng về phe Dân Làng (điểm cân bằng +{}).",
+            balance
+        ));
+    } else if balance < -BALANCE_TOLERANCE {
+        check.warnings.push(format!(
+            "Đội hình nghiêng về phe Ma Sói (điểm cân bằng {}).",
+            balance
+        ));
+    }
+
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles(entries: &[(RoleId, u8)]) -> HashMap<u8, u8> {
+        entries
+            .iter()
+            .map(|(role, count)| (*role as u8, *count))
+            .collect()
+    }
+
+    #[test]
+    fn rejects_unknown_and_dead_role_ids() {
+        assert!(!validate_composition(&HashMap::from([(99, 5)]), 5).is_valid());
+        assert!(!validate_composition(&roles(&[(RoleId::Dead, 5)]), 5).is_valid());
+    }
+
+    #[test]
+    fn role_total_must_match_player_count() {
+        let check =
+            validate_composition(&roles(&[(RoleId::Werewolf, 1), (RoleId::Villager, 3)]), 5);
+        assert!(!check.is_valid());
+    }
+
+    #[test]
+    fn any_wolf_faction_role_counts_as_a_wolf() {
+        let no_wolf = roles(&[(RoleId::Seer, 1), (RoleId::Villager, 4)]);
+        assert!(!validate_composition(&no_wolf, 5).is_valid());
+
+        let kitten_only = roles(&[
+            (RoleId::KittenWolf, 1),
+            (RoleId::Seer, 1),
+            (RoleId::Villager, 3),
+        ]);
+        assert!(validate_composition(&kitten_only, 5).is_valid());
+    }
+
+    #[test]
+    fn wolves_must_be_outnumbered() {
+        let check =
+            validate_composition(&roles(&[(RoleId::Werewolf, 2), (RoleId::Villager, 2)]), 4);
+        assert!(!check.is_valid());
+    }
+
+    #[test]
+    fn warns_without_information_roles() {
+        let check =
+            validate_composition(&roles(&[(RoleId::Werewolf, 1), (RoleId::Villager, 4)]), 5);
+        assert!(check.is_valid());
+        assert!(!check.warnings.is_empty());
+    }
+}